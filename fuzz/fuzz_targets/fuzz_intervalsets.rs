@@ -12,9 +12,10 @@ use fuzz_support::RangeType;
 ///
 /// This is meant for tests, and should be useless in normal code, as the
 /// various functions preserve those invariants.
-fn check_invariants<T>(intv: &IntervalSet<T>)
+fn check_invariants<T, P>(intv: &IntervalSet<T, P>)
 where
     T: PartialOrd + NothingBetween,
+    P: Policy<T>,
 {
     let mut it = intv.iter();
     if let Some(first) = it.next() {
@@ -41,8 +42,11 @@ struct Data {
     val: u32,
 }
 
-fuzz_target!(|data: Data| {
-    let mut m = IntervalSet::<u32>::default();
+fn check_policy<P>(data: &Data)
+where
+    P: Policy<u32> + ::core::fmt::Debug,
+{
+    let mut m = IntervalSet::<u32, P>::default();
     m.extend(data.set1.iter().map(|b| b.kind.build(b.lower, b.upper)));
     check_invariants(&m);
 
@@ -56,11 +60,19 @@ fuzz_target!(|data: Data| {
     _ = m.is_empty();
     _ = m.difference(data.val);
     _ = m.difference_interval(&intv);
-    _ = m.contains(&data.val);
+    assert_eq!(m.find(&data.val), m.iter().find(|v| v.contains(&data.val)));
+    assert!(m
+        .overlapping(&intv)
+        .iter()
+        .eq(m.iter().filter(|v| v.intersects(&intv))));
     _ = m.contains_interval(&intv);
     _ = m.intersects_interval(&intv);
     _ = m.intersection_interval(&intv);
     _ = m.convex_hull();
+    for gap in m.gaps_within(&intv) {
+        assert!(intv.contains_interval(&gap));
+        assert!(!m.intersects_interval(&gap));
+    }
     _ = m.left_of(&data.val);
     _ = m.strictly_left_of(&data.val);
     _ = m.left_of_interval(&intv);
@@ -68,15 +80,37 @@ fuzz_target!(|data: Data| {
     _ = m.right_of(&data.val);
     _ = m.strictly_right_of(&data.val);
     _ = m.right_of_interval(&intv);
-    _ = format!("{}", m);
     _ = format!("{:?}", m);
+    assert_eq!(
+        format!("{}", m).parse::<IntervalSet<u32, P>>(),
+        Ok(m.clone())
+    );
+    assert_eq!(
+        IntervalSet::<u32, P>::parse_postgres(&m.postgres().to_string()),
+        Ok(m.clone())
+    );
+    assert_eq!(
+        Interval::<u32>::parse_postgres(&intv.postgres().to_string()),
+        Ok(intv.clone())
+    );
+
+    // The tree-based sets must give the same result
+    let mut tree = BTreeIntervalSet::<u32, P>::default();
+    tree.extend(data.set1.iter().map(|b| b.kind.build(b.lower, b.upper)));
+    assert!(tree.iter().eq(m.iter()));
+    assert_eq!(tree.contains(&data.val), m.contains(&data.val));
+    assert!(tree.overlapping(&intv).eq(m.overlapping(&intv).iter()));
+    assert_eq!(tree.contains_interval(&intv), m.contains_interval(&intv));
 
     m.remove(data.val);
     m.remove_interval(&intv);
+    tree.remove(data.val);
+    tree.remove_interval(&intv);
+    assert!(tree.iter().eq(m.iter()));
 
     _ = m.iter().collect::<Vec<_>>();
 
-    let mut m2 = IntervalSet::<u32>::default();
+    let mut m2 = IntervalSet::<u32, P>::default();
     m2.extend(data.set2.iter().map(|b| b.kind.build(b.lower, b.upper)));
     check_invariants(&m2);
 
@@ -87,4 +121,48 @@ fuzz_target!(|data: Data| {
     _ = m.left_of_set(&m2);
     _ = m.right_of_set(&m2);
 
+    let inters = m.intersection_set(&m2);
+    check_invariants(&inters);
+
+    let union = m.union_set(&m2);
+    check_invariants(&union);
+    assert!(union.contains_set(&m));
+    assert!(union.contains_set(&m2));
+
+    let diff = m.difference_set(&m2);
+    check_invariants(&diff);
+    assert!(!diff.intersects_set(&m2));
+    assert!(m.contains_set(&diff));
+
+    let symdiff = m.symmetric_difference_set(&m2);
+    check_invariants(&symdiff);
+    assert!(!symdiff.intersects_set(&inters));
+
+    let compl = m.complement();
+    check_invariants(&compl);
+    assert!(!compl.intersects_set(&m));
+    assert!(m.union_set(&compl).complement().is_empty());
+}
+
+fuzz_target!(|data: Data| {
+    check_policy::<Joining>(&data);
+    check_policy::<Separating>(&data);
+    check_policy::<Splitting>(&data);
+
+    let m = IntervalSet::<u32>::new(
+        data.set1.iter().map(|b| b.kind.build(b.lower, b.upper)),
+    );
+
+    // Streaming operations must give the same result as the sets
+    let m2 = IntervalSet::<u32>::new(
+        data.set2.iter().map(|b| b.kind.build(b.lower, b.upper)),
+    );
+    let left = || m.iter().cloned();
+    let right = || m2.iter().cloned();
+    assert!(union_iter(left(), right()).eq(m.union_set(&m2).iter().cloned()));
+    assert!(intersection_iter(left(), right())
+        .eq(m.intersection_set(&m2).iter().cloned()));
+    assert!(difference_iter(left(), right())
+        .eq(m.difference_set(&m2).iter().cloned()));
+    assert!(merge_all([left(), right()]).eq(m.union_set(&m2).iter().cloned()));
 });
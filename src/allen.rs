@@ -0,0 +1,306 @@
+//! Allen's interval algebra.
+
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use ::core::cmp::Ordering;
+
+/// The thirteen relations of Allen's interval algebra, as returned by
+/// [`Interval::relation()`].
+///
+/// The relations consider which values are part of the intervals, so open
+/// and closed bounds matter.  Two intervals "meet" when there is no value
+/// between them, but they have no common value either:
+/// ```none
+///    [1, 3) meets [3, 5)
+///    [1, 3] meets (3, 5)
+///    [1, 3] overlaps [3, 5]
+///    [1, 3] meets [4, 5] for integers, since there is nothing between 3 and 4
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AllenRelation {
+    /// `[1, 2)` is before `[3, 4)`: there are values between them
+    Before,
+    /// `[1, 3)` meets `[3, 4)`: they have no common value, but there is
+    /// nothing between them
+    Meets,
+    /// `[1, 3)` overlaps `[2, 4)`
+    Overlaps,
+    /// `[1, 2)` starts `[1, 4)`
+    Starts,
+    /// `[2, 3)` is during `[1, 4)`
+    During,
+    /// `[3, 4)` finishes `[1, 4)`
+    Finishes,
+    /// The two intervals contain the same values
+    Equals,
+    /// Inverse of `Finishes`
+    FinishedBy,
+    /// Inverse of `During`
+    Contains,
+    /// Inverse of `Starts`
+    StartedBy,
+    /// Inverse of `Overlaps`
+    OverlappedBy,
+    /// Inverse of `Meets`
+    MetBy,
+    /// Inverse of `Before`
+    After,
+}
+
+impl AllenRelation {
+    /// All the relations
+    pub const ALL: [AllenRelation; 13] = [
+        AllenRelation::Before,
+        AllenRelation::Meets,
+        AllenRelation::Overlaps,
+        AllenRelation::Starts,
+        AllenRelation::During,
+        AllenRelation::Finishes,
+        AllenRelation::Equals,
+        AllenRelation::FinishedBy,
+        AllenRelation::Contains,
+        AllenRelation::StartedBy,
+        AllenRelation::OverlappedBy,
+        AllenRelation::MetBy,
+        AllenRelation::After,
+    ];
+
+    /// If `A self B`, returns the relation such that `B relation A`.
+    /// ```
+    /// #  use rust_intervals::AllenRelation;
+    ///    assert_eq!(AllenRelation::Meets.inverse(), AllenRelation::MetBy);
+    ///    assert_eq!(AllenRelation::Equals.inverse(), AllenRelation::Equals);
+    /// ```
+    #[must_use]
+    pub fn inverse(self) -> Self {
+        // Relations are declared so that the inverse is symmetric
+        AllenRelation::ALL
+            .get(12 - self as usize)
+            .copied()
+            .unwrap_or(self)
+    }
+
+    /// If `A self B` and `B other C`, returns all the possible relations
+    /// between A and C.
+    /// ```
+    /// #  use rust_intervals::{AllenRelation, AllenRelations};
+    ///    let r = AllenRelation::Meets.compose(AllenRelation::During);
+    ///    assert_eq!(
+    ///        r,
+    ///        [
+    ///            AllenRelation::Overlaps,
+    ///            AllenRelation::Starts,
+    ///            AllenRelation::During,
+    ///        ].iter().copied().collect::<AllenRelations>(),
+    ///    );
+    /// ```
+    #[must_use]
+    pub fn compose(self, other: Self) -> AllenRelations {
+        AllenRelations(
+            COMPOSITION
+                .get(self as usize)
+                .and_then(|row| row.get(other as usize))
+                .copied()
+                .unwrap_or(0),
+        )
+    }
+
+    fn mask(self) -> u16 {
+        1 << (self as u16)
+    }
+}
+
+/// A set of Allen relations, used to represent uncertain knowledge, for
+/// instance the result of [`AllenRelation::compose()`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AllenRelations(u16);
+
+impl AllenRelations {
+    /// The set that contains no relation
+    #[must_use]
+    pub fn empty() -> Self {
+        AllenRelations(0)
+    }
+
+    /// The set of all relations
+    #[must_use]
+    pub fn all() -> Self {
+        AllenRelations(0x1fff)
+    }
+
+    /// Whether the set contains the relation
+    #[must_use]
+    pub fn contains(&self, relation: AllenRelation) -> bool {
+        self.0 & relation.mask() != 0
+    }
+
+    /// Adds a relation to the set
+    pub fn insert(&mut self, relation: AllenRelation) {
+        self.0 |= relation.mask();
+    }
+
+    /// Whether the set contains no relation.  This means the constraints
+    /// it was computed from are inconsistent.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The number of relations in the set
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// The relations in either set
+    #[must_use]
+    pub fn union(&self, other: Self) -> Self {
+        AllenRelations(self.0 | other.0)
+    }
+
+    /// The relations in both sets
+    #[must_use]
+    pub fn intersection(&self, other: Self) -> Self {
+        AllenRelations(self.0 & other.0)
+    }
+
+    /// The inverse of all relations in the set
+    #[must_use]
+    pub fn inverse(&self) -> Self {
+        self.iter().map(AllenRelation::inverse).collect()
+    }
+
+    /// If A is related to B by one of the relations in self, and B is
+    /// related to C by one of the relations in other, returns all the
+    /// possible relations between A and C.
+    /// ```
+    /// #  use rust_intervals::{AllenRelation, AllenRelations};
+    ///    let r = AllenRelations::from(AllenRelation::Before)
+    ///        .compose(AllenRelation::Meets.into());
+    ///    assert_eq!(r, AllenRelation::Before.into());
+    /// ```
+    #[must_use]
+    pub fn compose(&self, other: Self) -> Self {
+        self.iter()
+            .flat_map(|r1| other.iter().map(move |r2| r1.compose(r2)))
+            .fold(AllenRelations::empty(), |acc, r| acc.union(r))
+    }
+
+    /// Iterate over all relations in the set
+    pub fn iter(&self) -> impl Iterator<Item = AllenRelation> + '_ {
+        AllenRelation::ALL
+            .iter()
+            .copied()
+            .filter(move |r| self.contains(*r))
+    }
+}
+
+impl From<AllenRelation> for AllenRelations {
+    fn from(relation: AllenRelation) -> Self {
+        AllenRelations(relation.mask())
+    }
+}
+
+impl ::core::iter::FromIterator<AllenRelation> for AllenRelations {
+    fn from_iter<I: IntoIterator<Item = AllenRelation>>(iter: I) -> Self {
+        let mut result = AllenRelations::empty();
+        for r in iter {
+            result.insert(r);
+        }
+        result
+    }
+}
+
+/// The composition table: `COMPOSITION[r1][r2]` is the set of possible
+/// relations between A and C when `A r1 B` and `B r2 C`.
+#[rustfmt::skip]
+const COMPOSITION: [[u16; 13]; 13] = [
+    // Before
+    [0x0001, 0x0001, 0x0001, 0x0001, 0x001f, 0x001f, 0x0001, 0x0001, 0x0001, 0x0001, 0x001f, 0x001f, 0x1fff],
+    // Meets
+    [0x0001, 0x0001, 0x0001, 0x0002, 0x001c, 0x001c, 0x0002, 0x0001, 0x0001, 0x0002, 0x001c, 0x00e0, 0x1f00],
+    // Overlaps
+    [0x0001, 0x0001, 0x0007, 0x0004, 0x001c, 0x001c, 0x0004, 0x0007, 0x0187, 0x0184, 0x07fc, 0x0700, 0x1f00],
+    // Starts
+    [0x0001, 0x0001, 0x0007, 0x0008, 0x0010, 0x0010, 0x0008, 0x0007, 0x0187, 0x0248, 0x0430, 0x0800, 0x1000],
+    // During
+    [0x0001, 0x0001, 0x001f, 0x0010, 0x0010, 0x0010, 0x0010, 0x001f, 0x1fff, 0x1c30, 0x1c30, 0x1000, 0x1000],
+    // Finishes
+    [0x0001, 0x0002, 0x001c, 0x0010, 0x0010, 0x0020, 0x0020, 0x00e0, 0x1f00, 0x1c00, 0x1c00, 0x1000, 0x1000],
+    // Equals
+    [0x0001, 0x0002, 0x0004, 0x0008, 0x0010, 0x0020, 0x0040, 0x0080, 0x0100, 0x0200, 0x0400, 0x0800, 0x1000],
+    // FinishedBy
+    [0x0001, 0x0002, 0x0004, 0x0004, 0x001c, 0x00e0, 0x0080, 0x0080, 0x0100, 0x0100, 0x0700, 0x0700, 0x1f00],
+    // Contains
+    [0x0187, 0x0184, 0x0184, 0x0184, 0x07fc, 0x0700, 0x0100, 0x0100, 0x0100, 0x0100, 0x0700, 0x0700, 0x1f00],
+    // StartedBy
+    [0x0187, 0x0184, 0x0184, 0x0248, 0x0430, 0x0400, 0x0200, 0x0100, 0x0100, 0x0200, 0x0400, 0x0800, 0x1000],
+    // OverlappedBy
+    [0x0187, 0x0184, 0x07fc, 0x0430, 0x0430, 0x0400, 0x0400, 0x0700, 0x1f00, 0x1c00, 0x1c00, 0x1000, 0x1000],
+    // MetBy
+    [0x0187, 0x0248, 0x0430, 0x0430, 0x0430, 0x0800, 0x0800, 0x0800, 0x1000, 0x1000, 0x1000, 0x1000, 0x1000],
+    // After
+    [0x1fff, 0x1c30, 0x1c30, 0x1c30, 0x1c30, 0x1000, 0x1000, 0x1000, 0x1000, 0x1000, 0x1000, 0x1000, 0x1000],
+];
+
+impl<T> Interval<T> {
+    /// The relation between self and other, in Allen's interval algebra.
+    /// Returns None if either interval is empty, or if the bounds cannot be
+    /// compared.
+    /// ```
+    /// #  use rust_intervals::{interval, AllenRelation};
+    ///    let intv1 = interval!(1, 3);
+    ///    assert_eq!(
+    ///        intv1.relation(interval!(3, 5)),
+    ///        Some(AllenRelation::Meets),
+    ///    );
+    ///    assert_eq!(
+    ///        intv1.relation(interval!(0, 10)),
+    ///        Some(AllenRelation::During),
+    ///    );
+    /// ```
+    pub fn relation<U>(&self, other: U) -> Option<AllenRelation>
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let o = other.borrow();
+        if self.is_empty() || o.is_empty() {
+            return None;
+        }
+
+        // Compare the end of one interval with the start of the other.  A
+        // lower bound and an upper bound are equal when there is nothing
+        // between them.
+        match self.upper.partial_cmp(&o.lower)? {
+            Ordering::Less => return Some(AllenRelation::Before),
+            Ordering::Equal => return Some(AllenRelation::Meets),
+            Ordering::Greater => {}
+        }
+        match self.lower.partial_cmp(&o.upper)? {
+            Ordering::Greater => return Some(AllenRelation::After),
+            Ordering::Equal => return Some(AllenRelation::MetBy),
+            Ordering::Less => {}
+        }
+        Some(
+            match (
+                self.lower.partial_cmp(&o.lower)?,
+                self.upper.partial_cmp(&o.upper)?,
+            ) {
+                (Ordering::Less, Ordering::Less) => AllenRelation::Overlaps,
+                (Ordering::Equal, Ordering::Less) => AllenRelation::Starts,
+                (Ordering::Greater, Ordering::Less) => AllenRelation::During,
+                (Ordering::Greater, Ordering::Equal) => AllenRelation::Finishes,
+                (Ordering::Equal, Ordering::Equal) => AllenRelation::Equals,
+                (Ordering::Less, Ordering::Equal) => AllenRelation::FinishedBy,
+                (Ordering::Less, Ordering::Greater) => AllenRelation::Contains,
+                (Ordering::Equal, Ordering::Greater) => {
+                    AllenRelation::StartedBy
+                }
+                (Ordering::Greater, Ordering::Greater) => {
+                    AllenRelation::OverlappedBy
+                }
+            },
+        )
+    }
+}
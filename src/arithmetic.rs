@@ -0,0 +1,681 @@
+//! Interval arithmetic.
+//!
+//! The result of an operation on two intervals is the smallest interval that
+//! contains the result of the operation on every pair of values from the
+//! two intervals.
+//!
+//! The `+`, `*` and `/` operators, and the unary `-`, are implemented
+//! between intervals and between an interval and a value.  They return None
+//! when the result cannot be represented.
+//!
+//! There is no operator for the arithmetic subtraction: the binary `-`
+//! operator between intervals has always computed their difference as sets
+//! (see [`Interval::difference()`]), and keeps doing so.  Use
+//! [`Interval::checked_sub()`] and [`Interval::checked_sub_scalar()`]
+//! instead.  To subtract an interval from a value, convert the value to an
+//! interval first.
+//! ```
+//! #  use rust_intervals::{interval, Interval, Pair};
+//!    let intv1 = interval!(1, 10, "[]");
+//!    let intv2 = interval!(5, 20, "[]");
+//!    assert_eq!(intv1 - intv2, Pair::One(interval!(1, 5, "[)")));
+//!    assert_eq!(intv1.checked_sub(intv2), Some(interval!(-19, 5, "[]")));
+//!    assert_eq!(intv1.checked_sub_scalar(1), Some(interval!(0, 9, "[]")));
+//!    assert_eq!(
+//!        Interval::new_single(10).checked_sub(interval!(3, "(inf")),
+//!        Some(interval!("-inf", 7, ")")),
+//!    );
+//! ```
+
+use crate::bounds::Bound;
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use crate::pairs::Pair;
+use ::core::cmp::Ordering;
+
+/// The operations on values needed for interval arithmetic.
+/// All the operations return None when the result cannot be represented,
+/// for instance on overflow or division by zero.
+pub trait Arithmetic: Sized {
+    /// The neutral element for addition
+    fn zero() -> Self;
+
+    fn checked_add(&self, rhs: &Self) -> Option<Self>;
+    fn checked_sub(&self, rhs: &Self) -> Option<Self>;
+    fn checked_mul(&self, rhs: &Self) -> Option<Self>;
+    fn checked_div(&self, rhs: &Self) -> Option<Self>;
+    fn checked_neg(&self) -> Option<Self>;
+
+    /// For discrete types, the value immediately after self.  This is used
+    /// to convert open bounds to closed bounds, so that results are as tight
+    /// as possible.  Continuous types should return None.
+    fn successor(&self) -> Option<Self> {
+        None
+    }
+
+    /// For discrete types, the value immediately before self.
+    fn predecessor(&self) -> Option<Self> {
+        None
+    }
+
+    /// Called on the lower bound computed by an operation.  Types subject to
+    /// rounding errors can return a smaller value, so that the result
+    /// encloses the exact result.
+    fn round_down(self) -> Self {
+        self
+    }
+
+    /// Called on the upper bound computed by an operation.
+    fn round_up(self) -> Self {
+        self
+    }
+}
+
+macro_rules! arithmetic_for_int {
+    ($t:tt) => {
+        impl Arithmetic for $t {
+            fn zero() -> Self {
+                0
+            }
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                $t::checked_add(*self, *rhs)
+            }
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                $t::checked_sub(*self, *rhs)
+            }
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                $t::checked_mul(*self, *rhs)
+            }
+            fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                $t::checked_div(*self, *rhs)
+            }
+            fn checked_neg(&self) -> Option<Self> {
+                $t::checked_neg(*self)
+            }
+            fn successor(&self) -> Option<Self> {
+                $t::checked_add(*self, 1)
+            }
+            fn predecessor(&self) -> Option<Self> {
+                $t::checked_sub(*self, 1)
+            }
+        }
+    };
+}
+
+/// For floats, operations only fail when they return NaN.
+macro_rules! arithmetic_for_float {
+    ($t:tt) => {
+        impl Arithmetic for $t {
+            fn zero() -> Self {
+                0.0
+            }
+            fn checked_add(&self, rhs: &Self) -> Option<Self> {
+                Some(self + rhs).filter(|r| !r.is_nan())
+            }
+            fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+                Some(self - rhs).filter(|r| !r.is_nan())
+            }
+            fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+                Some(self * rhs).filter(|r| !r.is_nan())
+            }
+            fn checked_div(&self, rhs: &Self) -> Option<Self> {
+                if *rhs == 0.0 {
+                    None
+                } else {
+                    Some(self / rhs).filter(|r| !r.is_nan())
+                }
+            }
+            fn checked_neg(&self) -> Option<Self> {
+                Some(-self)
+            }
+        }
+    };
+}
+
+arithmetic_for_int!(u8);
+arithmetic_for_int!(u16);
+arithmetic_for_int!(u32);
+arithmetic_for_int!(u64);
+arithmetic_for_int!(u128);
+arithmetic_for_int!(i8);
+arithmetic_for_int!(i16);
+arithmetic_for_int!(i32);
+arithmetic_for_int!(i64);
+arithmetic_for_int!(i128);
+arithmetic_for_int!(usize);
+arithmetic_for_int!(isize);
+arithmetic_for_float!(f32);
+arithmetic_for_float!(f64);
+
+/// Whether we are computing the lower or the upper bound of a result
+#[derive(Clone, Copy)]
+enum Side {
+    Lower,
+    Upper,
+}
+
+impl Side {
+    fn round<T: Arithmetic>(self, value: T) -> T {
+        match self {
+            Side::Lower => value.round_down(),
+            Side::Upper => value.round_up(),
+        }
+    }
+}
+
+/// One end of a non-empty interval, as used for arithmetic.
+#[derive(Clone)]
+enum End<T> {
+    NegInfinity,
+    Value(T, bool), // The value, and whether it belongs to the interval
+    PosInfinity,
+}
+
+impl<T> End<T>
+where
+    T: PartialOrd + Arithmetic,
+{
+    fn infinity(sign: Ordering) -> Self {
+        match sign {
+            Ordering::Less => End::NegInfinity,
+            Ordering::Equal | Ordering::Greater => End::PosInfinity,
+        }
+    }
+
+    /// If this end is zero, return whether it is included
+    fn as_zero(&self) -> Option<bool> {
+        match self {
+            End::Value(v, closed) if *v == T::zero() => Some(*closed),
+            End::NegInfinity | End::Value(..) | End::PosInfinity => None,
+        }
+    }
+
+    /// Compare with zero
+    fn sign(&self) -> Option<Ordering> {
+        match self {
+            End::NegInfinity => Some(Ordering::Less),
+            End::Value(v, _) => v.partial_cmp(&T::zero()),
+            End::PosInfinity => Some(Ordering::Greater),
+        }
+    }
+
+    /// Compare the values of two ends, ignoring whether they are included
+    fn cmp_value(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (End::Value(v1, _), End::Value(v2, _)) => v1.partial_cmp(v2),
+            (End::NegInfinity, End::NegInfinity)
+            | (End::PosInfinity, End::PosInfinity) => Some(Ordering::Equal),
+            (End::NegInfinity, _) | (_, End::PosInfinity) => {
+                Some(Ordering::Less)
+            }
+            (_, End::NegInfinity) | (End::PosInfinity, _) => {
+                Some(Ordering::Greater)
+            }
+        }
+    }
+
+    fn is_closed(&self) -> bool {
+        matches!(self, End::Value(_, true))
+    }
+
+    fn add(&self, rhs: &Self, side: Side) -> Option<Self> {
+        match (self, rhs) {
+            (End::Value(x, cx), End::Value(y, cy)) => {
+                Some(End::Value(side.round(x.checked_add(y)?), *cx && *cy))
+            }
+            (End::Value(..), inf) | (inf, _) => Some(inf.clone_infinity()),
+        }
+    }
+
+    fn sub(&self, rhs: &Self, side: Side) -> Option<Self> {
+        match (self, rhs) {
+            (End::Value(x, cx), End::Value(y, cy)) => {
+                Some(End::Value(side.round(x.checked_sub(y)?), *cx && *cy))
+            }
+            (End::Value(..), End::NegInfinity) => Some(End::PosInfinity),
+            (End::Value(..), End::PosInfinity) => Some(End::NegInfinity),
+            (inf, _) => Some(inf.clone_infinity()),
+        }
+    }
+
+    fn neg(&self) -> Option<Self> {
+        match self {
+            End::NegInfinity => Some(End::PosInfinity),
+            End::Value(x, c) => Some(End::Value(x.checked_neg()?, *c)),
+            End::PosInfinity => Some(End::NegInfinity),
+        }
+    }
+
+    fn mul(&self, rhs: &Self, side: Side) -> Option<Self> {
+        // Zero times anything (even infinity) is zero
+        match (self.as_zero(), rhs.as_zero()) {
+            (Some(true), _) | (_, Some(true)) => {
+                return Some(End::Value(T::zero(), true))
+            }
+            (Some(false), _) | (_, Some(false)) => {
+                return Some(End::Value(T::zero(), false))
+            }
+            (None, None) => {}
+        }
+        match (self, rhs) {
+            (End::Value(x, cx), End::Value(y, cy)) => {
+                Some(End::Value(side.round(x.checked_mul(y)?), *cx && *cy))
+            }
+            _ => Some(End::infinity(product(self.sign()?, rhs.sign()?))),
+        }
+    }
+
+    /// Divide by rhs, which is an end of an interval that doesn't contain
+    /// zero, and whose values have the given sign.
+    fn div(&self, rhs: &Self, rhs_sign: Ordering, side: Side) -> Option<Self> {
+        if let Some(closed) = self.as_zero() {
+            return Some(End::Value(T::zero(), closed));
+        }
+        match (self, rhs) {
+            (End::Value(x, cx), End::Value(y, cy))
+                if rhs.as_zero().is_none() =>
+            {
+                Some(End::Value(side.round(x.checked_div(y)?), *cx && *cy))
+            }
+            // Dividing by a value close to zero, or dividing infinity
+            (_, End::Value(..)) => {
+                Some(End::infinity(product(self.sign()?, rhs_sign)))
+            }
+            // Dividing by infinity.  When self is also infinite, the result
+            // could be anything, but the other ends will give infinity.
+            (_, End::NegInfinity | End::PosInfinity) => {
+                Some(End::Value(T::zero(), false))
+            }
+        }
+    }
+
+    /// Only called for infinite ends
+    fn clone_infinity(&self) -> Self {
+        match self {
+            End::NegInfinity => End::NegInfinity,
+            End::Value(..) | End::PosInfinity => End::PosInfinity,
+        }
+    }
+}
+
+/// The sign of the product of two non-zero values with the given signs
+fn product(s1: Ordering, s2: Ordering) -> Ordering {
+    if s1 == s2 {
+        Ordering::Greater
+    } else {
+        Ordering::Less
+    }
+}
+
+/// Return the smallest candidate for the lower bound, or the largest one
+/// for the upper bound.  When two candidates have the same value, the
+/// included one is preferred.
+fn extreme<T>(candidates: [Option<End<T>>; 4], side: Side) -> Option<End<T>>
+where
+    T: PartialOrd + Arithmetic,
+{
+    let wanted = match side {
+        Side::Lower => Ordering::Less,
+        Side::Upper => Ordering::Greater,
+    };
+    let mut result: Option<End<T>> = None;
+    for c in candidates {
+        let c = c?;
+        result = match result {
+            None => Some(c),
+            Some(r) => match c.cmp_value(&r)? {
+                Ordering::Equal if c.is_closed() => Some(c),
+                ord if ord == wanted => Some(c),
+                Ordering::Less | Ordering::Equal | Ordering::Greater => Some(r),
+            },
+        };
+    }
+    result
+}
+
+impl<T> Interval<T> {
+    /// The two ends of a non-empty interval.  For discrete types, open
+    /// bounds are converted to closed bounds.
+    fn ends(&self) -> (End<T>, End<T>)
+    where
+        T: Arithmetic + Clone,
+    {
+        let lo = match &self.lower {
+            Bound::LeftUnbounded => End::NegInfinity,
+            Bound::LeftOf(v) => End::Value(v.clone(), true),
+            Bound::RightOf(v) => match v.successor() {
+                Some(s) => End::Value(s, true),
+                None => End::Value(v.clone(), false),
+            },
+            Bound::RightUnbounded => End::PosInfinity,
+        };
+        let hi = match &self.upper {
+            Bound::LeftUnbounded => End::NegInfinity,
+            Bound::LeftOf(v) => match v.predecessor() {
+                Some(p) => End::Value(p, true),
+                None => End::Value(v.clone(), false),
+            },
+            Bound::RightOf(v) => End::Value(v.clone(), true),
+            Bound::RightUnbounded => End::PosInfinity,
+        };
+        (lo, hi)
+    }
+
+    fn from_ends(lo: &End<T>, hi: &End<T>) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        let lower = match lo {
+            End::NegInfinity => Bound::LeftUnbounded,
+            End::Value(v, true) => Bound::LeftOf(v.clone()),
+            End::Value(v, false) => Bound::RightOf(v.clone()),
+            End::PosInfinity => Bound::RightUnbounded,
+        };
+        let upper = match hi {
+            End::NegInfinity => Bound::LeftUnbounded,
+            End::Value(v, true) => Bound::RightOf(v.clone()),
+            End::Value(v, false) => Bound::LeftOf(v.clone()),
+            End::PosInfinity => Bound::RightUnbounded,
+        };
+        Interval::from_bounds(&lower, &upper)
+    }
+
+    /// Adds the two intervals, i.e. returns all values `x + y` where x is in
+    /// self and y is in rhs.
+    /// Returns None if the result cannot be represented, for instance on
+    /// overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1, 5, "[)");
+    ///    let intv2 = interval!(10, "(inf");
+    ///    assert_eq!(intv1.checked_add(&intv2), Some(interval!(11, "(inf")));
+    ///    assert_eq!(&intv1 + &intv2, Some(interval!(11, "(inf")));
+    ///    assert_eq!(intv1 + 2, Some(interval!(3, 7, "[)")));
+    ///    assert_eq!(interval!(1_u8, 255, "[]") + 1, None);
+    /// ```
+    pub fn checked_add<U>(&self, rhs: U) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let r = rhs.borrow();
+        if self.is_empty() || r.is_empty() {
+            return Some(Interval::empty());
+        }
+        let (a1, a2) = self.ends();
+        let (b1, b2) = r.ends();
+        Some(Interval::from_ends(
+            &a1.add(&b1, Side::Lower)?,
+            &a2.add(&b2, Side::Upper)?,
+        ))
+    }
+
+    /// Subtracts the two intervals, i.e. returns all values `x - y` where x
+    /// is in self and y is in rhs.
+    /// Note that the `-` operator computes the difference of the two
+    /// intervals as sets, see [`Interval::difference()`].
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(5_u32, 10, "[]");
+    ///    let intv2 = interval!(1, 2, "[]");
+    ///    assert_eq!(intv1.checked_sub(&intv2), Some(interval!(3, 9, "[]")));
+    ///    assert_eq!(intv2.checked_sub(&intv1), None);  // overflow
+    /// ```
+    pub fn checked_sub<U>(&self, rhs: U) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let r = rhs.borrow();
+        if self.is_empty() || r.is_empty() {
+            return Some(Interval::empty());
+        }
+        let (a1, a2) = self.ends();
+        let (b1, b2) = r.ends();
+        Some(Interval::from_ends(
+            &a1.sub(&b2, Side::Lower)?,
+            &a2.sub(&b1, Side::Upper)?,
+        ))
+    }
+
+    /// Subtracts a value from all values in self, i.e. returns all values
+    /// `x - value` where x is in self.  This is the same as
+    /// `checked_sub(Interval::new_single(value))`.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(5_u32, 10, "[)");
+    ///    assert_eq!(intv1.checked_sub_scalar(2), Some(interval!(3, 8, "[)")));
+    ///    assert_eq!(intv1.checked_sub_scalar(&6), None);  // overflow
+    /// ```
+    pub fn checked_sub_scalar<V>(&self, value: V) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        V: ::core::borrow::Borrow<T>,
+    {
+        self.checked_sub(Interval::new_single(value.borrow().clone()))
+    }
+
+    /// Multiplies the two intervals, i.e. returns all values `x * y` where
+    /// x is in self and y is in rhs.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(-2, 3, "[]");
+    ///    let intv2 = interval!(4, 5, "(]");
+    ///    assert_eq!(intv1.checked_mul(&intv2), Some(interval!(-10, 15, "[]")));
+    ///    assert_eq!(&intv1 * -1, Some(interval!(-3, 2, "[]")));
+    /// ```
+    pub fn checked_mul<U>(&self, rhs: U) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let r = rhs.borrow();
+        if self.is_empty() || r.is_empty() {
+            return Some(Interval::empty());
+        }
+        let (a1, a2) = self.ends();
+        let (b1, b2) = r.ends();
+        let corners = |side| {
+            extreme(
+                [
+                    a1.mul(&b1, side),
+                    a1.mul(&b2, side),
+                    a2.mul(&b1, side),
+                    a2.mul(&b2, side),
+                ],
+                side,
+            )
+        };
+        Some(Interval::from_ends(
+            &corners(Side::Lower)?,
+            &corners(Side::Upper)?,
+        ))
+    }
+
+    /// Divides the two intervals, i.e. returns all values `x / y` where
+    /// x is in self and y is a non-zero value in rhs.
+    /// When rhs contains zero, the result might be made of two disjoint
+    /// intervals.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, Pair};
+    ///    let intv1 = interval!(1.0, 2.0, "[]");
+    ///    assert_eq!(
+    ///        intv1.checked_div(interval!(2.0, 4.0, "[]")),
+    ///        Some(Pair::One(interval!(0.25, 1.0, "[]"))),
+    ///    );
+    ///    assert_eq!(
+    ///        intv1.checked_div(interval!(-1.0, 1.0, "[]")),
+    ///        Some(Pair::Two(
+    ///            interval!("-inf", -1.0, "]"),
+    ///            interval!(1.0, "[inf"),
+    ///        )),
+    ///    );
+    /// ```
+    pub fn checked_div<U>(&self, rhs: U) -> Option<Pair<T>>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let r = rhs.borrow();
+        let negative = r.intersection(Interval {
+            lower: Bound::LeftUnbounded,
+            upper: Bound::LeftOf(T::zero()),
+        });
+        let positive = r.intersection(Interval {
+            lower: Bound::RightOf(T::zero()),
+            upper: Bound::RightUnbounded,
+        });
+        let neg = self.div_same_sign(&negative, Ordering::Less)?;
+        let pos = self.div_same_sign(&positive, Ordering::Greater)?;
+        if neg.is_empty() || pos.is_empty() {
+            Some(Pair::new_from_two(neg, pos))
+        } else if let Some(u) = neg.union(&pos) {
+            Some(Pair::One(u))
+        } else if neg.strictly_left_of_interval(&pos) {
+            Some(Pair::Two(neg, pos))
+        } else {
+            Some(Pair::Two(pos, neg))
+        }
+    }
+
+    /// Divides by an interval whose values all have the given sign.
+    fn div_same_sign(&self, rhs: &Self, sign: Ordering) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+    {
+        if self.is_empty() || rhs.is_empty() {
+            return Some(Interval::empty());
+        }
+        let (a1, a2) = self.ends();
+        let (b1, b2) = rhs.ends();
+        let corners = |side| {
+            extreme(
+                [
+                    a1.div(&b1, sign, side),
+                    a1.div(&b2, sign, side),
+                    a2.div(&b1, sign, side),
+                    a2.div(&b2, sign, side),
+                ],
+                side,
+            )
+        };
+        Some(Interval::from_ends(
+            &corners(Side::Lower)?,
+            &corners(Side::Upper)?,
+        ))
+    }
+
+    /// Returns all values `-x` where x is in self.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv = interval!(1, 5, "[)");
+    ///    assert_eq!(intv.checked_neg(), Some(interval!(-5, -1, "(]")));
+    ///    assert_eq!(-intv, Some(interval!(-5, -1, "(]")));
+    /// ```
+    pub fn checked_neg(&self) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+    {
+        if self.is_empty() {
+            return Some(Interval::empty());
+        }
+        let (a1, a2) = self.ends();
+        Some(Interval::from_ends(&a2.neg()?, &a1.neg()?))
+    }
+}
+
+/// Implements one of the arithmetic operators, between intervals or between
+/// an interval and a value.
+macro_rules! arithmetic_operator {
+    ($trait:ident, $method:ident, $checked:ident, $output:ty) => {
+        impl<T> ::core::ops::$trait<Interval<T>> for Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: Interval<T>) -> Self::Output {
+                self.$checked(rhs)
+            }
+        }
+
+        impl<T> ::core::ops::$trait<&Interval<T>> for Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: &Interval<T>) -> Self::Output {
+                self.$checked(rhs)
+            }
+        }
+
+        impl<T> ::core::ops::$trait<Interval<T>> for &Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: Interval<T>) -> Self::Output {
+                self.$checked(rhs)
+            }
+        }
+
+        impl<T> ::core::ops::$trait<&Interval<T>> for &Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: &Interval<T>) -> Self::Output {
+                self.$checked(rhs)
+            }
+        }
+
+        impl<T> ::core::ops::$trait<T> for Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$checked(Interval::new_single(rhs))
+            }
+        }
+
+        impl<T> ::core::ops::$trait<T> for &Interval<T>
+        where
+            T: PartialOrd + NothingBetween + Clone + Arithmetic,
+        {
+            type Output = Option<$output>;
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$checked(Interval::new_single(rhs))
+            }
+        }
+    };
+}
+
+arithmetic_operator!(Add, add, checked_add, Interval<T>);
+arithmetic_operator!(Mul, mul, checked_mul, Interval<T>);
+arithmetic_operator!(Div, div, checked_div, Pair<T>);
+
+///   -Interval
+impl<T> ::core::ops::Neg for Interval<T>
+where
+    T: PartialOrd + NothingBetween + Clone + Arithmetic,
+{
+    type Output = Option<Interval<T>>;
+
+    /// Same as [`Interval::checked_neg()`]
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
+
+///   -&Interval
+impl<T> ::core::ops::Neg for &Interval<T>
+where
+    T: PartialOrd + NothingBetween + Clone + Arithmetic,
+{
+    type Output = Option<Interval<T>>;
+
+    /// Same as [`Interval::checked_neg()`]
+    fn neg(self) -> Self::Output {
+        self.checked_neg()
+    }
+}
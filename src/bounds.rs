@@ -7,10 +7,37 @@ use ::core::cmp::{Ordering, PartialOrd};
 /// the value and its predecessor value.
 /// Likewise, RightOf represents a conceptual point halfway between the value
 /// and its successor.
-pub(crate) enum Bound<T> {
+///
+/// Contrary to `core::ops::Bound`, the same representation is used for
+/// lower and upper bounds, so that bounds can be compared with each other.
+/// A closed lower bound is `LeftOf(value)`, a closed upper bound is
+/// `RightOf(value)`.  An empty interval has a `RightUnbounded` lower bound
+/// and a `LeftUnbounded` upper bound.
+/// See [`Interval::lower_bound()`] and [`Interval::from_bounds()`].
+/// ```
+/// #  use rust_intervals::{interval, Bound, Interval};
+///    let intv1 = interval!(1, 10, "[)");
+///    assert!(matches!(intv1.lower_bound(), Bound::LeftOf(1)));
+///    assert!(matches!(intv1.upper_bound(), Bound::LeftOf(10)));
+///    assert_eq!(
+///        Interval::from_bounds(&Bound::RightOf(1), &Bound::RightUnbounded),
+///        interval!(1, "(inf"),
+///    );
+/// ```
+///
+/// [`Interval::lower_bound()`]: crate::Interval::lower_bound
+/// [`Interval::from_bounds()`]: crate::Interval::from_bounds
+pub enum Bound<T> {
+    /// Left of all values, i.e. -infinity
     LeftUnbounded,
+
+    /// Between the value and its predecessor
     LeftOf(T),
+
+    /// Between the value and its successor
     RightOf(T),
+
+    /// Right of all values, i.e. +infinity
     RightUnbounded,
 }
 
@@ -43,15 +70,80 @@ impl<T> Bound<T> {
 
     /// Return the bound's value (which might be included in the interval
     /// or not).  This returns None for an unbounded bound.
-    pub(crate) fn value(&self) -> Option<&T> {
+    pub fn value(&self) -> Option<&T> {
         match self {
             Bound::LeftUnbounded | Bound::RightUnbounded => None,
             Bound::LeftOf(p) | Bound::RightOf(p) => Some(p),
         }
     }
 
+    /// Converts a RightOf bound to the equivalent LeftOf bound, by moving
+    /// to the next value.  This is left unchanged if there is no next value.
+    pub(crate) fn canonical(&self) -> Self
+    where
+        T: Step + Clone,
+    {
+        match self {
+            Bound::RightOf(point) => match point.forward(1) {
+                Some(next) => Bound::LeftOf(next),
+                None => self.clone(),
+            },
+            Bound::LeftUnbounded | Bound::LeftOf(_) | Bound::RightUnbounded => {
+                self.clone()
+            }
+        }
+    }
+
+    /// Applies an increasing function to the bound's value, preserving the
+    /// kind of bound.
+    pub(crate) fn map<U, F>(&self, f: &mut F) -> Bound<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        match self {
+            Bound::LeftUnbounded => Bound::LeftUnbounded,
+            Bound::LeftOf(point) => Bound::LeftOf(f(point)),
+            Bound::RightOf(point) => Bound::RightOf(f(point)),
+            Bound::RightUnbounded => Bound::RightUnbounded,
+        }
+    }
+
+    /// Applies a decreasing function to the bound's value.  The bound is
+    /// mirrored, so that a lower bound becomes an upper bound with the same
+    /// inclusiveness, and conversely.
+    pub(crate) fn map_decreasing<U, F>(&self, f: &mut F) -> Bound<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        self.map(f).mirror()
+    }
+
+    /// Same as [`Bound::map()`], for a function that might fail.
+    pub(crate) fn checked_map<U, F>(&self, f: &mut F) -> Option<Bound<U>>
+    where
+        F: FnMut(&T) -> Option<U>,
+    {
+        Some(match self {
+            Bound::LeftUnbounded => Bound::LeftUnbounded,
+            Bound::LeftOf(point) => Bound::LeftOf(f(point)?),
+            Bound::RightOf(point) => Bound::RightOf(f(point)?),
+            Bound::RightUnbounded => Bound::RightUnbounded,
+        })
+    }
+
+    /// Converts a lower bound to the upper bound with the same value and
+    /// inclusiveness, or conversely.
+    pub(crate) fn mirror(self) -> Self {
+        match self {
+            Bound::LeftUnbounded => Bound::RightUnbounded,
+            Bound::LeftOf(point) => Bound::RightOf(point),
+            Bound::RightOf(point) => Bound::LeftOf(point),
+            Bound::RightUnbounded => Bound::LeftUnbounded,
+        }
+    }
+
     /// Converts from `Bound<T>` to `Bound<&T>`
-    pub(crate) fn as_ref(&self) -> Bound<&T> {
+    pub fn as_ref(&self) -> Bound<&T> {
         match self {
             Bound::LeftUnbounded => Bound::LeftUnbounded,
             Bound::LeftOf(point) => Bound::LeftOf(point),
@@ -160,6 +252,18 @@ where
     }
 }
 
+impl<T> Ord for Bound<T>
+where
+    T: Ord + NothingBetween,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        match self.partial_cmp(other) {
+            None => unreachable!(),
+            Some(order) => order,
+        }
+    }
+}
+
 impl<T> PartialOrd<Bound<&T>> for Bound<T>
 where
     T: PartialOrd + NothingBetween,
@@ -1,5 +1,8 @@
+use crate::measure::Measure;
 use crate::nothing_between::NothingBetween;
 use crate::step::{Bounded, Step};
+use crate::translate::Translate;
+use ::core::convert::TryFrom;
 
 impl<T: chrono::TimeZone> NothingBetween for chrono::DateTime<T> {
     fn nothing_between(&self, other: &Self) -> bool {
@@ -36,11 +39,114 @@ impl Step for chrono::NaiveDate {
     }
 }
 
+impl<T: chrono::TimeZone> Translate<chrono::TimeDelta> for chrono::DateTime<T> {
+    fn checked_add_delta(&self, delta: &chrono::TimeDelta) -> Option<Self> {
+        self.clone().checked_add_signed(*delta)
+    }
+    fn checked_sub_delta(&self, delta: &chrono::TimeDelta) -> Option<Self> {
+        self.clone().checked_sub_signed(*delta)
+    }
+}
+
+impl<T: chrono::TimeZone> Translate<chrono::Days> for chrono::DateTime<T> {
+    fn checked_add_delta(&self, delta: &chrono::Days) -> Option<Self> {
+        self.clone().checked_add_days(*delta)
+    }
+    fn checked_sub_delta(&self, delta: &chrono::Days) -> Option<Self> {
+        self.clone().checked_sub_days(*delta)
+    }
+}
+
+impl Translate<chrono::TimeDelta> for chrono::NaiveDate {
+    fn checked_add_delta(&self, delta: &chrono::TimeDelta) -> Option<Self> {
+        self.checked_add_signed(*delta)
+    }
+    fn checked_sub_delta(&self, delta: &chrono::TimeDelta) -> Option<Self> {
+        self.checked_sub_signed(*delta)
+    }
+}
+
+impl Translate<chrono::Days> for chrono::NaiveDate {
+    fn checked_add_delta(&self, delta: &chrono::Days) -> Option<Self> {
+        self.checked_add_days(*delta)
+    }
+    fn checked_sub_delta(&self, delta: &chrono::Days) -> Option<Self> {
+        self.checked_sub_days(*delta)
+    }
+}
+
+/// Adjusts the length of an interval of a discrete type, where each value
+/// has the given duration.
+fn discrete_length(
+    mut length: chrono::TimeDelta,
+    unit: chrono::TimeDelta,
+    inclusive: (bool, bool),
+) -> Option<chrono::TimeDelta> {
+    if !inclusive.0 {
+        length = length.checked_sub(&unit)?;
+    }
+    if inclusive.1 {
+        length = length.checked_add(&unit)?;
+    }
+    Some(length)
+}
+
+impl<T: chrono::TimeZone> Measure for chrono::DateTime<T> {
+    type Output = chrono::TimeDelta;
+    fn measure(
+        &self,
+        upper: &Self,
+        inclusive: (bool, bool),
+    ) -> Option<Self::Output> {
+        discrete_length(
+            upper.clone().signed_duration_since(self),
+            chrono::TimeDelta::nanoseconds(1),
+            inclusive,
+        )
+    }
+    fn add_lengths(
+        length1: &Self::Output,
+        length2: &Self::Output,
+    ) -> Option<Self::Output> {
+        length1.checked_add(length2)
+    }
+    fn fraction(
+        length: &Self::Output,
+        numerator: u32,
+        denominator: u32,
+    ) -> Option<Self::Output> {
+        length
+            .checked_div(i32::try_from(denominator).ok()?)?
+            .checked_mul(i32::try_from(numerator).ok()?)
+    }
+}
+
+impl Measure for chrono::NaiveDate {
+    type Output = chrono::TimeDelta;
+    fn measure(
+        &self,
+        upper: &Self,
+        inclusive: (bool, bool),
+    ) -> Option<Self::Output> {
+        discrete_length(
+            upper.signed_duration_since(*self),
+            chrono::TimeDelta::days(1),
+            inclusive,
+        )
+    }
+    fn add_lengths(
+        length1: &Self::Output,
+        length2: &Self::Output,
+    ) -> Option<Self::Output> {
+        length1.checked_add(length2)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::tests::test::check_empty;
     use crate::*;
-    use ::chrono::{Local, NaiveDate, TimeDelta};
+    use ::chrono::{Days, Local, NaiveDate, TimeDelta};
 
     #[test]
     fn test_chrono() {
@@ -60,6 +166,10 @@ mod test {
             now + TimeDelta::seconds(1),
             now + TimeDelta::minutes(1),
         );
+        assert_eq!(
+            interval!(now, now + TimeDelta::minutes(1)).length(),
+            Some(TimeDelta::minutes(1)),
+        );
     }
 
     #[test]
@@ -76,6 +186,11 @@ mod test {
             [feb_27, feb_28, feb_29, mar_01, mar_02]
         );
         assert_eq!(intv1.iter().size_hint(), (5, Some(5)));
+        assert_eq!(intv1.length(), Some(TimeDelta::days(5)));
+        assert_eq!(
+            interval!(feb_27, mar_03, "[]").length(),
+            Some(TimeDelta::days(6)),
+        );
 
         let intv1 = interval!(feb_27, mar_03);
         assert_eq!(
@@ -90,4 +205,73 @@ mod test {
             [NaiveDate::MAX],
         );
     }
+    #[test]
+    fn test_chrono_map() {
+        let apr_01 = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let apr_10 = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        let midnight =
+            |d: &NaiveDate| d.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        assert_eq!(
+            interval!(apr_01, apr_10).map(midnight),
+            interval!(midnight(&apr_01), midnight(&apr_10)),
+        );
+    }
+    #[test]
+    fn test_chrono_translate() {
+        let apr_01 = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+        let apr_03 = NaiveDate::from_ymd_opt(2024, 4, 3).unwrap();
+        let apr_05 = NaiveDate::from_ymd_opt(2024, 4, 5).unwrap();
+        let apr_07 = NaiveDate::from_ymd_opt(2024, 4, 7).unwrap();
+        let intv1 = interval!(apr_01, apr_05, "[]");
+        assert_eq!(
+            intv1.shift(&Days::new(2)),
+            Some(interval!(apr_03, apr_07, "[]"))
+        );
+        assert_eq!(
+            intv1.shift(&TimeDelta::days(2)),
+            Some(interval!(apr_03, apr_07, "[]")),
+        );
+        assert_eq!(
+            intv1.shrink(&Days::new(2)),
+            Some(interval!(apr_03, apr_03, "[]"))
+        );
+        assert_eq!(
+            interval!(apr_01, NaiveDate::MAX, "[]").shift(&Days::new(1)),
+            None
+        );
+
+        let now = Local::now();
+        let intv2 = interval!(now, now + TimeDelta::hours(1));
+        assert_eq!(
+            intv2.expand(&TimeDelta::minutes(1)),
+            Some(interval!(
+                now - TimeDelta::minutes(1),
+                now + TimeDelta::minutes(61)
+            )),
+        );
+        assert_eq!(
+            intv2.shift(&Days::new(1)).and_then(|i| i.length()),
+            Some(TimeDelta::hours(1)),
+        );
+        assert_eq!(
+            intv2
+                .partition_by_length(4)
+                .map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                interval!(now, now + TimeDelta::minutes(15)),
+                interval!(
+                    now + TimeDelta::minutes(15),
+                    now + TimeDelta::minutes(30)
+                ),
+                interval!(
+                    now + TimeDelta::minutes(30),
+                    now + TimeDelta::minutes(45)
+                ),
+                interval!(
+                    now + TimeDelta::minutes(45),
+                    now + TimeDelta::hours(1)
+                ),
+            ]),
+        );
+    }
 }
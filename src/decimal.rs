@@ -1,11 +1,70 @@
+use crate::arithmetic::Arithmetic;
+use crate::measure::Measure;
 use crate::nothing_between::NothingBetween;
+use crate::translate::Translate;
+use rust_decimal::Decimal;
 
-impl NothingBetween for rust_decimal::Decimal {
+impl NothingBetween for Decimal {
     fn nothing_between(&self, _other: &Self) -> bool {
         false // there is always a decimal between two others
     }
 }
 
+impl Arithmetic for Decimal {
+    fn zero() -> Self {
+        Decimal::ZERO
+    }
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_add(*self, *rhs)
+    }
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_sub(*self, *rhs)
+    }
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_mul(*self, *rhs)
+    }
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        Decimal::checked_div(*self, *rhs)
+    }
+    fn checked_neg(&self) -> Option<Self> {
+        Some(-*self)
+    }
+}
+
+impl Translate for Decimal {
+    fn checked_add_delta(&self, delta: &Self) -> Option<Self> {
+        Decimal::checked_add(*self, *delta)
+    }
+    fn checked_sub_delta(&self, delta: &Self) -> Option<Self> {
+        Decimal::checked_sub(*self, *delta)
+    }
+}
+
+impl Measure for Decimal {
+    type Output = Decimal;
+    fn measure(
+        &self,
+        upper: &Self,
+        _inclusive: (bool, bool),
+    ) -> Option<Self::Output> {
+        Decimal::checked_sub(*upper, *self)
+    }
+    fn add_lengths(
+        length1: &Self::Output,
+        length2: &Self::Output,
+    ) -> Option<Self::Output> {
+        Decimal::checked_add(*length1, *length2)
+    }
+    fn fraction(
+        length: &Self::Output,
+        numerator: u32,
+        denominator: u32,
+    ) -> Option<Self::Output> {
+        Decimal::checked_div(*length, Decimal::from(denominator))?
+            .checked_mul(Decimal::from(numerator))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::*;
@@ -16,5 +75,21 @@ mod test {
         let dec2 = rust_decimal::Decimal::new(101, 2); // 1.01
         assert!(interval!(dec1, dec1).is_empty());
         assert!(!interval!(dec1, dec2, "()").is_empty());
+        assert_eq!(
+            interval!(dec1, dec2, "()") + dec1,
+            Some(interval!(dec1 + dec1, dec2 + dec1, "()")),
+        );
+        assert_eq!(
+            interval!(dec1, dec2, "()").length(),
+            Some(rust_decimal::Decimal::new(1, 2)),
+        );
+        let split = interval!(dec1, dec2, "[]").partition_by_length(2);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                interval!(dec1, rust_decimal::Decimal::new(1005, 3)),
+                interval!(rust_decimal::Decimal::new(1005, 3), dec2, "[]"),
+            ]),
+        );
     }
 }
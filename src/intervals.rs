@@ -1,7 +1,10 @@
 use crate::bounds::Bound;
 use crate::iterator::IntervalIterator;
+use crate::measure::Measure;
+use crate::notation::{parse_notation, Notation, NotationDisplay};
 use crate::nothing_between::NothingBetween;
 use crate::pairs::Pair;
+use crate::postgres::{parse_range, Postgres};
 use crate::step::{Bounded, Step};
 use ::core::cmp::{Ordering, PartialOrd};
 use ::core::ops::{Bound as RgBound, RangeBounds};
@@ -355,35 +358,246 @@ impl<T> Interval<T> {
     where
         T: PartialOrd + NothingBetween + Clone + Bounded,
     {
-        match (range.start_bound(), range.end_bound()) {
-            (RgBound::Included(lo), RgBound::Included(up)) => {
-                Interval::new_closed_closed(lo.clone(), up.clone())
-            }
-            (RgBound::Included(lo), RgBound::Excluded(up)) => {
-                Interval::new_closed_open(lo.clone(), up.clone())
-            }
-            (RgBound::Excluded(lo), RgBound::Included(up)) => {
-                Interval::new_open_closed(lo.clone(), up.clone())
-            }
-            (RgBound::Excluded(lo), RgBound::Excluded(up)) => {
-                Interval::new_open_open(lo.clone(), up.clone())
-            }
-            (RgBound::Unbounded, RgBound::Included(up)) => {
-                Interval::new_unbounded_closed(up.clone())
-            }
-            (RgBound::Unbounded, RgBound::Excluded(up)) => {
-                Interval::new_unbounded_open(up.clone())
-            }
-            (RgBound::Unbounded, RgBound::Unbounded) => {
-                Interval::doubly_unbounded()
-            }
-            (RgBound::Included(lo), RgBound::Unbounded) => {
-                Interval::new_closed_unbounded(lo.clone())
+        Interval::from((
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        ))
+    }
+
+    /// Same as [`Interval::from_range()`], but reports invalid bounds
+    /// instead of returning an empty interval, see [`BoundsError`].
+    /// Bounds that are equal are accepted, so `[1, 1)` is still an empty
+    /// interval, as in PostgreSQL.
+    /// ```
+    /// #  use rust_intervals::{interval, BoundsError, Interval};
+    ///    assert_eq!(Interval::try_from_range(1..5), Ok(interval!(1, 5)));
+    ///    assert_eq!(Interval::try_from_range(1..1), Ok(Interval::empty()));
+    ///    assert_eq!(Interval::try_from_range(5..1), Err(BoundsError::Inverted));
+    ///    assert_eq!(
+    ///        Interval::try_from_range(..f32::NAN),
+    ///        Err(BoundsError::Unordered),
+    ///    );
+    ///    assert_eq!(
+    ///        Interval::try_from_range(1.0..f32::INFINITY),
+    ///        Err(BoundsError::Infinite),
+    ///    );
+    /// ```
+    pub fn try_from_range<R: RangeBounds<T>>(
+        range: R,
+    ) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Clone + Bounded,
+    {
+        Interval::try_from_bounds(
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+        )
+    }
+
+    /// Checks the bounds before creating the interval
+    fn try_from_bounds(
+        lower: RgBound<T>,
+        upper: RgBound<T>,
+    ) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        let check = |bound: &RgBound<T>| match bound {
+            RgBound::Unbounded => Ok(()),
+            RgBound::Included(v) | RgBound::Excluded(v) => {
+                match (
+                    v.partial_cmp(&T::min_value()),
+                    v.partial_cmp(&T::max_value()),
+                ) {
+                    (None, _) | (_, None) => Err(BoundsError::Unordered),
+                    (Some(Ordering::Less), _)
+                    | (_, Some(Ordering::Greater)) => {
+                        Err(BoundsError::Infinite)
+                    }
+                    (Some(Ordering::Equal | Ordering::Greater), _) => Ok(()),
+                }
             }
-            (RgBound::Excluded(lo), RgBound::Unbounded) => {
-                Interval::new_open_unbounded(lo.clone())
+        };
+        check(&lower)?;
+        check(&upper)?;
+        if let (
+            RgBound::Included(lo) | RgBound::Excluded(lo),
+            RgBound::Included(up) | RgBound::Excluded(up),
+        ) = (&lower, &upper)
+        {
+            if lo > up {
+                return Err(BoundsError::Inverted);
             }
         }
+        Ok(Interval::from((lower, upper)))
+    }
+
+    /// Same as [`Interval::new_closed_open()`], but reports invalid bounds,
+    /// see [`Interval::try_from_range()`].
+    /// ```
+    /// #  use rust_intervals::{interval, BoundsError, Interval};
+    ///    assert_eq!(Interval::try_new_closed_open(1, 5), Ok(interval!(1, 5)));
+    ///    assert_eq!(
+    ///        Interval::try_new_closed_open(5, 1),
+    ///        Err(BoundsError::Inverted),
+    ///    );
+    /// ```
+    pub fn try_new_closed_open(lower: T, upper: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(
+            RgBound::Included(lower),
+            RgBound::Excluded(upper),
+        )
+    }
+
+    /// Same as [`Interval::new_closed_closed()`], but reports invalid bounds
+    pub fn try_new_closed_closed(
+        lower: T,
+        upper: T,
+    ) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(
+            RgBound::Included(lower),
+            RgBound::Included(upper),
+        )
+    }
+
+    /// Same as [`Interval::new_open_open()`], but reports invalid bounds
+    pub fn try_new_open_open(lower: T, upper: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(
+            RgBound::Excluded(lower),
+            RgBound::Excluded(upper),
+        )
+    }
+
+    /// Same as [`Interval::new_open_closed()`], but reports invalid bounds
+    pub fn try_new_open_closed(lower: T, upper: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(
+            RgBound::Excluded(lower),
+            RgBound::Included(upper),
+        )
+    }
+
+    /// Same as [`Interval::new_unbounded_closed()`], but reports invalid
+    /// bounds
+    pub fn try_new_unbounded_closed(upper: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(RgBound::Unbounded, RgBound::Included(upper))
+    }
+
+    /// Same as [`Interval::new_unbounded_open()`], but reports invalid
+    /// bounds
+    pub fn try_new_unbounded_open(upper: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(RgBound::Unbounded, RgBound::Excluded(upper))
+    }
+
+    /// Same as [`Interval::new_closed_unbounded()`], but reports invalid
+    /// bounds
+    pub fn try_new_closed_unbounded(lower: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(RgBound::Included(lower), RgBound::Unbounded)
+    }
+
+    /// Same as [`Interval::new_open_unbounded()`], but reports invalid
+    /// bounds
+    pub fn try_new_open_unbounded(lower: T) -> Result<Self, BoundsError>
+    where
+        T: PartialOrd + NothingBetween + Bounded,
+    {
+        Interval::try_from_bounds(RgBound::Excluded(lower), RgBound::Unbounded)
+    }
+
+    /// Returns the bounds of the interval, as used by the standard library.
+    /// Returns None for an empty interval, which has no bounds.
+    /// See also [`Interval::into_bounds()`], and [`Interval::lower_bound()`]
+    /// for the bounds as used by this crate.
+    ///
+    /// The interval itself implements `RangeBounds`, but panics when it is
+    /// empty.  The result of this function is a safe alternative.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    use ::core::ops::Bound;
+    ///    use std::collections::BTreeMap;
+    ///    assert_eq!(
+    ///        interval!(1, 10, "(]").bounds(),
+    ///        Some((Bound::Excluded(&1), Bound::Included(&10))),
+    ///    );
+    ///    assert_eq!(
+    ///        interval!("-inf", 10).bounds(),
+    ///        Some((Bound::Unbounded, Bound::Excluded(&10))),
+    ///    );
+    ///    assert_eq!(Interval::<u32>::empty().bounds(), None);
+    ///
+    ///    let map = BTreeMap::from([(1, "a"), (5, "b"), (10, "c")]);
+    ///    let intv1 = interval!(1, 10, "()");
+    ///    if let Some(bounds) = intv1.bounds() {
+    ///        assert_eq!(map.range(bounds).collect::<Vec<_>>(), vec![(&5, &"b")]);
+    ///    }
+    /// ```
+    pub fn bounds(&self) -> Option<(RgBound<&T>, RgBound<&T>)> {
+        self.as_ref().into_bounds()
+    }
+
+    /// Converts the interval to its bounds, as used by the standard library.
+    /// Returns None for an empty interval, which has no bounds.  Use
+    /// `Interval::from` for the reverse conversion.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    use ::core::ops::Bound;
+    ///    let bounds = interval!(1, 10, "[)").into_bounds();
+    ///    assert_eq!(bounds, Some((Bound::Included(1), Bound::Excluded(10))));
+    ///    assert_eq!(Interval::from(bounds.unwrap()), interval!(1, 10, "[)"));
+    ///
+    ///    let mut v = vec![0, 1, 2, 3, 4, 5];
+    ///    if let Some(bounds) = interval!(1_usize, 3, "[]").into_bounds() {
+    ///        assert_eq!(v.drain(bounds).collect::<Vec<_>>(), vec![1, 2, 3]);
+    ///    }
+    ///    assert_eq!(v, vec![0, 4, 5]);
+    /// ```
+    pub fn into_bounds(self) -> Option<(RgBound<T>, RgBound<T>)> {
+        let lower = match self.lower {
+            Bound::LeftUnbounded => RgBound::Unbounded,
+            Bound::LeftOf(lo) => RgBound::Included(lo),
+            Bound::RightOf(lo) => RgBound::Excluded(lo),
+            Bound::RightUnbounded => return None,
+        };
+        let upper = match self.upper {
+            Bound::LeftUnbounded => return None,
+            Bound::LeftOf(up) => RgBound::Excluded(up),
+            Bound::RightOf(up) => RgBound::Included(up),
+            Bound::RightUnbounded => RgBound::Unbounded,
+        };
+        Some((lower, upper))
+    }
+
+    /// The lower bound, including whether it is open or closed.  This is
+    /// `RightUnbounded` for an empty interval.
+    /// See also [`Interval::bounds()`].
+    pub fn lower_bound(&self) -> &Bound<T> {
+        &self.lower
+    }
+
+    /// The upper bound, including whether it is open or closed.  This is
+    /// `LeftUnbounded` for an empty interval.
+    pub fn upper_bound(&self) -> &Bound<T> {
+        &self.upper
     }
 
     /// The lower bound.  Returns None for an unbounded interval (i.e. lower
@@ -443,6 +657,29 @@ impl<T> Interval<T> {
         }
     }
 
+    /// The length of the interval, see [`Measure`].
+    /// Returns None if the interval is unbounded, or if its length cannot
+    /// be represented.  Empty intervals have a zero length.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    assert_eq!(interval!(1_u8, 10, "[)").length(), Some(9));
+    ///    assert_eq!(interval!(1_u8, 10, "(]").length(), Some(9));
+    ///    assert_eq!(interval!(0_u8, 255, "[]").length(), None);
+    ///    assert_eq!(Interval::<u8>::empty().length(), Some(0));
+    /// ```
+    pub fn length(&self) -> Option<T::Output>
+    where
+        T: Measure,
+    {
+        if self.is_empty() {
+            return Some(T::Output::default());
+        }
+        self.lower()?.measure(
+            self.upper()?,
+            (self.lower_inclusive(), self.upper_inclusive()),
+        )
+    }
+
     /// Whether value is contained in the interval.
     /// You can pass either a T or &T, for convenience.
     /// ```
@@ -887,6 +1124,164 @@ impl<T> Interval<T> {
         }
     }
 
+    /// The size of the gap between the two intervals, i.e. the length of
+    /// [`Interval::between()`].  This is zero when the intervals overlap or
+    /// touch.  For discrete types, this is the number of values in the gap,
+    /// see [`Measure`].
+    /// Returns None if either interval is empty, or if the size cannot be
+    /// represented.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1.0, 3.0);
+    ///    assert_eq!(intv1.distance_to(interval!(5.0, 8.0)), Some(2.0));
+    ///    assert_eq!(intv1.distance_to(interval!(3.0, 8.0)), Some(0.0));
+    ///    assert_eq!(interval!(1, 3).distance_to(interval!(5, 8)), Some(2_u32));
+    ///    assert_eq!(
+    ///        interval!(1, 3, "[]").distance_to(interval!(5, 8)),
+    ///        Some(1_u32),
+    ///    );
+    /// ```
+    pub fn distance_to<U>(&self, other: U) -> Option<T::Output>
+    where
+        T: PartialOrd + NothingBetween + Clone + Measure,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        let o = other.borrow();
+        if self.is_empty() || o.is_empty() {
+            None
+        } else {
+            self.between(o).length()
+        }
+    }
+
+    /// The size of the gap between the interval and the value.  This is
+    /// zero if the value is in the interval or immediately next to it.
+    /// Returns None if the interval is empty, or if the size cannot be
+    /// represented.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1.0, 3.0);
+    ///    assert_eq!(intv1.distance_to_value(&5.0), Some(2.0));
+    ///    assert_eq!(intv1.distance_to_value(&2.0), Some(0.0));
+    ///    assert_eq!(interval!(1, 3).distance_to_value(&10), Some(7_u32));
+    /// ```
+    pub fn distance_to_value(&self, value: &T) -> Option<T::Output>
+    where
+        T: PartialOrd + NothingBetween + Clone + Measure,
+    {
+        self.distance_to(Interval::new_single(value.clone()))
+    }
+
+    /// The value in the interval that is closest to value, i.e. value itself
+    /// if it is in the interval, or one of the bounds otherwise.  Open bounds
+    /// are converted with [`Step`].
+    /// Returns None if the interval is empty.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1, 10);
+    ///    assert_eq!(intv1.nearest_value(&5), Some(5));
+    ///    assert_eq!(intv1.nearest_value(&-8), Some(1));
+    ///    assert_eq!(intv1.nearest_value(&20), Some(9));
+    /// ```
+    pub fn nearest_value(&self, value: &T) -> Option<T>
+    where
+        T: PartialOrd + NothingBetween + Clone + Step,
+    {
+        if self.is_empty() {
+            None
+        } else if !self.lower.left_of(value) {
+            match &self.lower {
+                Bound::LeftOf(lo) => Some(lo.clone()),
+                Bound::RightOf(lo) => lo.forward(1),
+                Bound::LeftUnbounded | Bound::RightUnbounded => None,
+            }
+        } else if !self.upper.right_of(value) {
+            match &self.upper {
+                Bound::LeftOf(up) => up.backward(1),
+                Bound::RightOf(up) => Some(up.clone()),
+                Bound::LeftUnbounded | Bound::RightUnbounded => None,
+            }
+        } else {
+            Some(value.clone())
+        }
+    }
+
+    /// Returns all values that are not in self.  Since the result might
+    /// consist of two disjoint intervals, this returns a [`Pair`].  The
+    /// [`Bounded`] trait is used so that, for instance, the complement of
+    /// `[0, 255]` for `u8` is empty.
+    /// This is the same as the [`!`] operator.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, Pair};
+    ///    let intv1 = interval!(1, 20);
+    ///    assert_eq!(
+    ///        intv1.complement(),
+    ///        Pair::Two(interval!("-inf", 1, ")"), interval!(20, "[inf")),
+    ///    );
+    ///    assert_eq!(!intv1, intv1.complement());
+    ///    assert_eq!(!&intv1, intv1.complement());
+    ///
+    ///    let intv2 = interval!(0_u8, 255, "[]");
+    ///    assert_eq!(intv2.complement(), Pair::One(Interval::empty()));
+    /// ```
+    pub fn complement(&self) -> Pair<T>
+    where
+        T: PartialOrd + NothingBetween + Clone + Bounded,
+    {
+        if self.is_empty() {
+            return Pair::One(Interval::doubly_unbounded());
+        }
+        Pair::new_from_two(
+            Interval::left_of_bound(&self.lower),
+            Interval::right_of_bound(&self.upper),
+        )
+    }
+
+    /// Returns all values of domain that are not in self.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, Pair};
+    ///    let intv1 = interval!(5, 10);
+    ///    assert_eq!(
+    ///        intv1.complement_within(interval!(0, 8)),
+    ///        Pair::One(interval!(0, 5)),
+    ///    );
+    /// ```
+    pub fn complement_within<U>(&self, domain: U) -> Pair<T>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Self>,
+    {
+        domain.borrow().difference(self)
+    }
+
+    /// All values to the left of the bound (which is the lower bound of some
+    /// interval).  Bounded is used to return an empty interval when there
+    /// are no valid values.
+    pub(crate) fn left_of_bound(bound: &Bound<T>) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone + Bounded,
+    {
+        if *bound <= Bound::LeftOf(T::min_value()) {
+            Interval::empty()
+        } else {
+            Interval::from_bounds(&Bound::LeftUnbounded, bound)
+        }
+    }
+
+    /// All values to the right of the bound (which is the upper bound of
+    /// some interval).  Bounded is used to return an empty interval when
+    /// there are no valid values.
+    pub(crate) fn right_of_bound(bound: &Bound<T>) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone + Bounded,
+    {
+        if *bound >= Bound::RightOf(T::max_value()) {
+            Interval::empty()
+        } else {
+            Interval::from_bounds(bound, &Bound::RightUnbounded)
+        }
+    }
+
     /// If neither interval is empty, returns true if no value lies between
     /// them.  True if either of the intervals is empty.
     pub fn contiguous<U>(&self, right: U) -> bool
@@ -929,6 +1324,141 @@ impl<T> Interval<T> {
         }
     }
 
+    /// The number of values in the interval.
+    /// Contrary to [`ExactSizeIterator::len()`], this doesn't panic when
+    /// the number of values is larger than usize.
+    /// Returns None if this number is larger than u128::MAX (only possible
+    /// for 128-bit types), or if it cannot be computed.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    assert_eq!(interval!(1, 10, "(]").cardinality(), Some(9));
+    ///    assert_eq!(
+    ///        Interval::<u64>::doubly_unbounded().cardinality(),
+    ///        Some(u128::from(u64::MAX) + 1),
+    ///    );
+    ///    assert_eq!(Interval::<u128>::doubly_unbounded().cardinality(), None);
+    /// ```
+    pub fn cardinality(&self) -> Option<u128>
+    where
+        T: Step + Bounded + PartialOrd + NothingBetween,
+    {
+        if self.is_empty() {
+            return Some(0);
+        }
+        let min = T::min_value();
+        let max = T::max_value();
+        let (lo, lo_incl) = match &self.lower {
+            Bound::LeftOf(lo) => (lo, true),
+            Bound::RightOf(lo) => (lo, false),
+            Bound::LeftUnbounded | Bound::RightUnbounded => (&min, true),
+        };
+        let (up, up_incl) = match &self.upper {
+            Bound::LeftOf(up) => (up, false),
+            Bound::RightOf(up) => (up, true),
+            Bound::LeftUnbounded | Bound::RightUnbounded => (&max, true),
+        };
+        let between = lo.elements_between_u128(up)?;
+        match (lo_incl, up_incl) {
+            (true, true) => between.checked_add(1),
+            (false, false) => between.checked_sub(1),
+            (true, false) | (false, true) => Some(between),
+        }
+    }
+
+    /// Returns an equivalent interval in closed-open form `[a, b)`, as done
+    /// by PostgreSQL for discrete ranges.  Open lower bounds and closed upper
+    /// bounds are moved to the next value with [`Step`], so that equal
+    /// intervals are displayed and serialized identically.
+    /// A closed upper bound is kept when there is no next value (for
+    /// instance `u8::MAX`), and unbounded sides are left unchanged.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    assert_eq!(interval!(1, 3, "[]").canonicalize().to_string(), "[1, 4)");
+    ///    assert_eq!(interval!(0, 4, "()").canonicalize().to_string(), "[1, 4)");
+    ///    assert_eq!(
+    ///        interval!("-inf", 3_u8, "]").canonicalize().to_string(),
+    ///        "(, 4)",
+    ///    );
+    ///    assert_eq!(
+    ///        interval!(1_u8, 255, "[]").canonicalize().to_string(),
+    ///        "[1, 255]",
+    ///    );
+    /// ```
+    #[must_use]
+    pub fn canonicalize(&self) -> Self
+    where
+        T: Clone + Step,
+    {
+        if self.is_empty() {
+            Interval::empty()
+        } else {
+            Interval {
+                lower: self.lower.canonical(),
+                upper: self.upper.canonical(),
+            }
+        }
+    }
+
+    /// Applies an increasing function to both bounds, for instance to
+    /// convert between units.  Each bound keeps its kind (open, closed or
+    /// unbounded).
+    /// If f is not strictly increasing, the result might be empty.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let seconds = interval!(1, 5, "(]");
+    ///    assert_eq!(seconds.map(|s| s * 1000), interval!(1000, 5000, "(]"));
+    ///    assert_eq!(
+    ///        interval!(1_u8, "inf").map(|v| f32::from(*v)),
+    ///        interval!(1.0, "inf"),
+    ///    );
+    /// ```
+    pub fn map<U, F>(&self, mut f: F) -> Interval<U>
+    where
+        U: PartialOrd + NothingBetween,
+        F: FnMut(&T) -> U,
+    {
+        if self.is_empty() {
+            return Interval::empty();
+        }
+        let lower = self.lower.map(&mut f);
+        let upper = self.upper.map(&mut f);
+        if lower >= upper {
+            Interval::empty()
+        } else {
+            Interval { lower, upper }
+        }
+    }
+
+    /// Applies a decreasing function to both bounds.  The bounds are
+    /// swapped, and so is their inclusiveness, so that the new lower bound
+    /// is `f(upper)`.
+    /// If f is not strictly decreasing, the result might be empty.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1, 5, "[)");
+    ///    assert_eq!(intv1.map_decreasing(|v| -v), interval!(-5, -1, "(]"));
+    ///    assert_eq!(
+    ///        interval!(1, "inf").map_decreasing(|v| 10 - v),
+    ///        interval!("-inf", 9, "]"),
+    ///    );
+    /// ```
+    pub fn map_decreasing<U, F>(&self, mut f: F) -> Interval<U>
+    where
+        U: PartialOrd + NothingBetween,
+        F: FnMut(&T) -> U,
+    {
+        if self.is_empty() {
+            return Interval::empty();
+        }
+        let lower = self.upper.map_decreasing(&mut f);
+        let upper = self.lower.map_decreasing(&mut f);
+        if lower >= upper {
+            Interval::empty()
+        } else {
+            Interval { lower, upper }
+        }
+    }
+
     /// Provides iteration over all values in the interval.
     /// ```
     /// #  use rust_intervals::{interval, Interval};
@@ -944,8 +1474,113 @@ impl<T> Interval<T> {
         IntervalIterator { intv: self.clone() }
     }
 
-    /// Creates an interval from its bounds, and normalizes empty intervals
-    pub(crate) fn from_bounds(lower: &Bound<T>, upper: &Bound<T>) -> Self
+    /// Displays the interval using the PostgreSQL text format for ranges.
+    /// Contrary to [`Display`](::core::fmt::Display), there are no spaces
+    /// around the bounds, and the bounds are quoted when they contain special
+    /// characters, so that the output can be sent to PostgreSQL and parsed
+    /// back with [`Interval::parse_postgres()`].
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    assert_eq!(interval!(1, 10).postgres().to_string(), "[1,10)");
+    ///    assert_eq!(interval!(1, "(inf").postgres().to_string(), "(1,)");
+    ///    assert_eq!(
+    ///        Interval::new_closed_closed('"', ',').postgres().to_string(),
+    ///        r#"["""",","]"#,
+    ///    );
+    /// ```
+    #[must_use]
+    pub fn postgres(&self) -> Postgres<'_, Self> {
+        Postgres(self)
+    }
+
+    /// Displays the interval using one of the supported notations.
+    /// ```
+    /// #  use rust_intervals::{interval, Notation};
+    ///    let intv = interval!(1, 5, "[]");
+    ///    assert_eq!(intv.display(Notation::Rust).to_string(), "1..=5");
+    ///    assert_eq!(intv.display(Notation::Iso31_11).to_string(), "[1, 5]");
+    /// ```
+    #[must_use]
+    pub fn display(&self, notation: Notation) -> NotationDisplay<'_, T> {
+        NotationDisplay {
+            intv: self,
+            notation,
+        }
+    }
+
+    /// Parses an interval written with the PostgreSQL text format for
+    /// ranges, as output by [`Interval::postgres()`], for instance `[1,10)`,
+    /// `(,5]`, `empty` or `["a,b","c\"d")`.
+    /// As in PostgreSQL, whitespace is allowed around the range, but any
+    /// whitespace within the brackets is part of the bounds.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, ParseError};
+    /// #  fn main() -> Result<(), ParseError<::core::num::ParseIntError>> {
+    ///    assert_eq!(Interval::parse_postgres(" [1,10) ")?, interval!(1, 10));
+    ///    assert_eq!(
+    ///        Interval::parse_postgres(r#"["1","10"]"#)?,
+    ///        interval!(1, 10, "[]"),
+    ///    );
+    ///    assert!(Interval::<u32>::parse_postgres("[1, 10)").is_err());
+    /// #  Ok(())
+    /// #  }
+    /// ```
+    pub fn parse_postgres<E>(s: &str) -> Result<Self, ParseErrorAt<E>>
+    where
+        T: PartialOrd
+            + Bounded
+            + NothingBetween
+            + ::core::str::FromStr<Err = E>,
+    {
+        parse_range(s, 0)
+    }
+
+    /// Parses an interval written with one of the supported notations.
+    /// Using [`Notation::Default`] is the same as using `parse()`, and
+    /// [`Notation::Postgres`] the same as [`Interval::parse_postgres()`],
+    /// but errors also report where they were detected.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, Notation, ParseError};
+    /// #  fn main() -> Result<(), ParseError<::core::num::ParseIntError>> {
+    ///    assert_eq!(
+    ///        Interval::parse_with("..=5", Notation::Rust)?,
+    ///        interval!("-inf", 5, "]"),
+    ///    );
+    ///    assert_eq!(
+    ///        Interval::parse_with("]1, 5[", Notation::Iso31_11)?,
+    ///        interval!(1, 5, "()"),
+    ///    );
+    /// #  Ok(())
+    /// #  }
+    /// ```
+    pub fn parse_with<E>(
+        s: &str,
+        notation: Notation,
+    ) -> Result<Self, ParseErrorAt<E>>
+    where
+        T: PartialOrd
+            + Bounded
+            + NothingBetween
+            + ::core::str::FromStr<Err = E>,
+    {
+        parse_notation(s, notation)
+    }
+
+    /// Creates an interval from its bounds, and normalizes empty intervals.
+    /// See [`Bound`] for the meaning of each bound, and
+    /// [`Interval::from()`] to use the bounds of the standard library.
+    /// ```
+    /// #  use rust_intervals::{interval, Bound, Interval};
+    ///    assert_eq!(
+    ///        Interval::from_bounds(&Bound::LeftOf(1), &Bound::RightOf(5)),
+    ///        interval!(1, 5, "[]"),
+    ///    );
+    ///    assert_eq!(
+    ///        Interval::from_bounds(&Bound::LeftOf(5), &Bound::LeftOf(1)),
+    ///        Interval::empty(),
+    ///    );
+    /// ```
+    pub fn from_bounds(lower: &Bound<T>, upper: &Bound<T>) -> Self
     where
         T: PartialOrd + NothingBetween + Clone,
     {
@@ -1093,6 +1728,32 @@ where
     }
 }
 
+///   !&Interval
+impl<T> ::core::ops::Not for &Interval<T>
+where
+    T: PartialOrd + NothingBetween + Clone + Bounded,
+{
+    type Output = Pair<T>;
+
+    /// Same as [`Interval::complement()`]
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
+///   !Interval
+impl<T> ::core::ops::Not for Interval<T>
+where
+    T: PartialOrd + NothingBetween + Clone + Bounded,
+{
+    type Output = Pair<T>;
+
+    /// Same as [`Interval::complement()`]
+    fn not(self) -> Self::Output {
+        self.complement()
+    }
+}
+
 impl<T> ::core::clone::Clone for Interval<T>
 where
     T: Clone,
@@ -1150,7 +1811,7 @@ where
     /// It has no real geometrical meaning.
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match self.lower.partial_cmp(&other.lower) {
-            None => unreachable!(), // interval is empty
+            None => None, // bounds cannot be compared, e.g. NaN
             Some(Ordering::Less) => Some(Ordering::Less),
             Some(Ordering::Greater) => Some(Ordering::Greater),
             Some(Ordering::Equal) => self.upper.partial_cmp(&other.upper),
@@ -1226,10 +1887,205 @@ where
     }
 }
 
+/// The error returned by the fallible constructors, like
+/// [`Interval::try_new_closed_open()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundsError {
+    /// The lower bound is greater than the upper bound.
+    Inverted,
+
+    /// A bound cannot be compared, for instance a float NaN.
+    Unordered,
+
+    /// A bound is outside of the range given by [`Bounded`], for instance
+    /// an infinite float.  Use an unbounded interval instead.
+    Infinite,
+}
+
+impl ::core::fmt::Display for BoundsError {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            BoundsError::Inverted => {
+                write!(f, "lower bound is greater than upper bound")
+            }
+            BoundsError::Unordered => write!(f, "bound cannot be compared"),
+            BoundsError::Infinite => write!(f, "bound is not finite"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for BoundsError {}
+
+/// The errors that can occur when parsing an interval or a set of intervals.
+///
+/// More variants might be added in future versions, so matches must include
+/// a wildcard arm.
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum ParseError<E> {
-    InvalidInput, // An invalid string was provided
-    Bound(E),     // An error while parsing bounds
+    InvalidInput,          // An invalid string was provided
+    Bound(E),              // An error while parsing bounds
+    InvalidMember(usize),  // Invalid string for the n-th interval of a set
+    MemberBound(usize, E), // Error while parsing bounds of n-th interval
+}
+
+impl<E> ParseError<E> {
+    /// Report this error for the n-th interval of a set
+    #[cfg(feature = "std")]
+    pub(crate) fn for_member(self, index: usize) -> Self {
+        match self {
+            ParseError::Bound(e) | ParseError::MemberBound(_, e) => {
+                ParseError::MemberBound(index, e)
+            }
+            ParseError::InvalidInput | ParseError::InvalidMember(_) => {
+                ParseError::InvalidMember(index)
+            }
+        }
+    }
+}
+
+impl<E> ::core::fmt::Display for ParseError<E>
+where
+    E: ::core::fmt::Display,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match self {
+            ParseError::InvalidInput => write!(f, "invalid input"),
+            ParseError::Bound(e) => write!(f, "invalid bound: {}", e),
+            ParseError::InvalidMember(idx) => {
+                write!(f, "invalid interval #{}", idx)
+            }
+            ParseError::MemberBound(idx, e) => {
+                write!(f, "invalid bound in interval #{}: {}", idx, e)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> ::std::error::Error for ParseError<E>
+where
+    E: ::std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match self {
+            ParseError::InvalidInput | ParseError::InvalidMember(_) => None,
+            ParseError::Bound(e) | ParseError::MemberBound(_, e) => Some(e),
+        }
+    }
+}
+
+/// A [`ParseError`], along with the position in the input where it was
+/// detected.  This is returned by [`Interval::parse_with()`] and
+/// [`Interval::parse_postgres()`], and converts to a [`ParseError`] with
+/// `?`.
+/// ```
+/// #  use rust_intervals::{Interval, ParseError};
+///    let err = Interval::<u32>::parse_postgres("[1,a)").unwrap_err();
+///    assert_eq!(err.offset(), 3);
+///    assert!(matches!(err.error(), ParseError::Bound(_)));
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct ParseErrorAt<E> {
+    offset: usize,
+    error: ParseError<E>,
+}
+
+impl<E> ParseErrorAt<E> {
+    /// The input does not follow the expected syntax at offset
+    pub(crate) fn invalid(offset: usize) -> Self {
+        ParseErrorAt {
+            offset,
+            error: ParseError::InvalidInput,
+        }
+    }
+
+    /// The bound starting at offset could not be parsed
+    pub(crate) fn bound(offset: usize, error: E) -> Self {
+        ParseErrorAt {
+            offset,
+            error: ParseError::Bound(error),
+        }
+    }
+
+    /// Report this error for a string that starts at offset in the input
+    pub(crate) fn shifted(self, offset: usize) -> Self {
+        ParseErrorAt {
+            offset: self.offset + offset,
+            error: self.error,
+        }
+    }
+
+    /// Report this error for the n-th interval of a set
+    #[cfg(feature = "std")]
+    pub(crate) fn for_member(self, index: usize) -> Self {
+        ParseErrorAt {
+            offset: self.offset,
+            error: self.error.for_member(index),
+        }
+    }
+
+    /// The position, in bytes, of the first unexpected character or of the
+    /// start of the invalid bound.  This is the length of the input if it
+    /// ended too early.
+    #[must_use]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The kind of error
+    #[must_use]
+    pub fn error(&self) -> &ParseError<E> {
+        &self.error
+    }
+
+    /// Discards the position, and returns the kind of error
+    #[must_use]
+    pub fn into_error(self) -> ParseError<E> {
+        self.error
+    }
+}
+
+impl<E> ::core::convert::From<ParseErrorAt<E>> for ParseError<E> {
+    fn from(value: ParseErrorAt<E>) -> Self {
+        value.error
+    }
+}
+
+impl<E> ::core::fmt::Display for ParseErrorAt<E>
+where
+    E: ::core::fmt::Display,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        let offset = self.offset;
+        match &self.error {
+            ParseError::InvalidInput => {
+                write!(f, "invalid input at byte {}", offset)
+            }
+            ParseError::Bound(e) => {
+                write!(f, "invalid bound at byte {}: {}", offset, e)
+            }
+            ParseError::InvalidMember(idx) => {
+                write!(f, "invalid interval #{} at byte {}", idx, offset)
+            }
+            ParseError::MemberBound(idx, e) => write!(
+                f,
+                "invalid bound in interval #{} at byte {}: {}",
+                idx, offset, e
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E> ::std::error::Error for ParseErrorAt<E>
+where
+    E: ::std::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        self.error.source()
+    }
 }
 
 impl<T, E> ::core::str::FromStr for Interval<T>
@@ -1242,70 +2098,84 @@ where
     /// It assumes the first occurrence of ',' in the string is the separator
     /// for the two bounds of the interval, and is not part of the display for
     /// one of the bounds.
+    /// See [`Interval::parse_postgres()`] for bounds that contain special
+    /// characters.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s == "empty" || s.is_empty() {
-            return Ok(Interval::empty());
-        }
+        Ok(parse_default(s)?)
+    }
+}
 
-        let mut input = s.char_indices();
-        let lo_incl = match input.next() {
-            None => unreachable!(),
-            Some((_, lo_incl)) => lo_incl,
-        };
-        let mut up_incl: char = ']';
-        let mut lo: Option<T> = None;
-        let mut up: Option<T> = None;
-        let mut start_offset: Option<usize> = None;
-
-        for (c_offset, c) in input {
-            if c == ',' {
-                match start_offset {
-                    None => lo = None,
-                    Some(offs) => {
-                        lo = match s[offs..c_offset].trim() {
-                            "" => None,
-                            a => Some(
-                                a.parse::<T>()
-                                    .map_err(|e| ParseError::Bound(e))?,
-                            ),
-                        };
-                    }
-                }
-                start_offset = None;
-            } else if c == ']' || c == ')' {
-                match start_offset {
-                    None => up = None,
-                    Some(offs) => {
-                        up = match s[offs..c_offset].trim() {
-                            "" => None,
-                            a => Some(
-                                a.parse::<T>()
-                                    .map_err(|e| ParseError::Bound(e))?,
-                            ),
-                        };
-                    }
-                }
-                up_incl = c;
-            } else if start_offset.is_none() {
-                start_offset = Some(c_offset);
+/// Parse the output of Display, for instance `[1, 4)`, `(, 5]` or `empty`.
+pub(crate) fn parse_default<T, E>(
+    s: &str,
+) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    if s == "empty" || s.is_empty() {
+        return Ok(Interval::empty());
+    }
+
+    let mut input = s.char_indices();
+    let lo_incl = match input.next() {
+        None => unreachable!(),
+        Some((_, lo_incl)) => lo_incl,
+    };
+    let mut up_incl: char = ']';
+    let mut lo: Option<T> = None;
+    let mut up: Option<T> = None;
+    let mut start_offset: Option<usize> = None;
+
+    // Parse the bound in s[offs..end], or None if it only contains spaces
+    let parse =
+        |offs: usize, end: usize| -> Result<Option<T>, ParseErrorAt<E>> {
+            let raw = s.get(offs..end).unwrap_or("");
+            match raw.trim() {
+                "" => Ok(None),
+                a => a.parse::<T>().map(Some).map_err(|e| {
+                    ParseErrorAt::bound(
+                        offs + raw.len() - raw.trim_start().len(),
+                        e,
+                    )
+                }),
             }
-        }
+        };
 
-        Ok(match (lo_incl, lo, up, up_incl) {
-            ('[', Some(lo), Some(up), ']') => {
-                Interval::new_closed_closed(lo, up)
-            }
-            ('[', Some(lo), Some(up), ')') => Interval::new_closed_open(lo, up),
-            ('(', Some(lo), Some(up), ')') => Interval::new_open_open(lo, up),
-            ('(', Some(lo), Some(up), ']') => Interval::new_open_closed(lo, up),
-            ('(', Some(lo), None, ')') => Interval::new_open_unbounded(lo),
-            ('[', Some(lo), None, ')') => Interval::new_closed_unbounded(lo),
-            ('(', None, Some(up), ')') => Interval::new_unbounded_open(up),
-            ('(', None, Some(up), ']') => Interval::new_unbounded_closed(up),
-            ('(', None, None, ')') => Interval::doubly_unbounded(),
-            _ => Err(ParseError::InvalidInput)?,
-        })
+    for (c_offset, c) in input {
+        if c == ',' {
+            lo = match start_offset {
+                None => None,
+                Some(offs) => parse(offs, c_offset)?,
+            };
+            start_offset = None;
+        } else if c == ']' || c == ')' {
+            up = match start_offset {
+                None => None,
+                Some(offs) => parse(offs, c_offset)?,
+            };
+            up_incl = c;
+        } else if start_offset.is_none() {
+            start_offset = Some(c_offset);
+        }
     }
+
+    Ok(match (lo_incl, lo, up, up_incl) {
+        ('[', Some(lo), Some(up), ']') => Interval::new_closed_closed(lo, up),
+        ('[', Some(lo), Some(up), ')') => Interval::new_closed_open(lo, up),
+        ('(', Some(lo), Some(up), ')') => Interval::new_open_open(lo, up),
+        ('(', Some(lo), Some(up), ']') => Interval::new_open_closed(lo, up),
+        ('(', Some(lo), None, ')') => Interval::new_open_unbounded(lo),
+        ('[', Some(lo), None, ')') => Interval::new_closed_unbounded(lo),
+        ('(', None, Some(up), ')') => Interval::new_unbounded_open(up),
+        ('(', None, Some(up), ']') => Interval::new_unbounded_closed(up),
+        ('(', None, None, ')') => Interval::doubly_unbounded(),
+        _ => {
+            // Either the closing bracket is missing, or the brackets do not
+            // match the bounds.
+            let closed = s.ends_with(&[']', ')'][..]);
+            Err(ParseErrorAt::invalid(if closed { 0 } else { s.len() }))?
+        }
+    })
 }
 
 impl<T> ::core::convert::From<::core::ops::Range<T>> for Interval<T>
@@ -1348,6 +2218,94 @@ where
         Interval::new_closed_unbounded(value.start.clone())
     }
 }
+impl<T> ::core::convert::From<(RgBound<T>, RgBound<T>)> for Interval<T>
+where
+    T: PartialOrd + NothingBetween + Bounded,
+{
+    fn from(value: (RgBound<T>, RgBound<T>)) -> Self {
+        match value {
+            (RgBound::Included(lo), RgBound::Included(up)) => {
+                Interval::new_closed_closed(lo, up)
+            }
+            (RgBound::Included(lo), RgBound::Excluded(up)) => {
+                Interval::new_closed_open(lo, up)
+            }
+            (RgBound::Excluded(lo), RgBound::Included(up)) => {
+                Interval::new_open_closed(lo, up)
+            }
+            (RgBound::Excluded(lo), RgBound::Excluded(up)) => {
+                Interval::new_open_open(lo, up)
+            }
+            (RgBound::Unbounded, RgBound::Included(up)) => {
+                Interval::new_unbounded_closed(up)
+            }
+            (RgBound::Unbounded, RgBound::Excluded(up)) => {
+                Interval::new_unbounded_open(up)
+            }
+            (RgBound::Unbounded, RgBound::Unbounded) => {
+                Interval::doubly_unbounded()
+            }
+            (RgBound::Included(lo), RgBound::Unbounded) => {
+                Interval::new_closed_unbounded(lo)
+            }
+            (RgBound::Excluded(lo), RgBound::Unbounded) => {
+                Interval::new_open_unbounded(lo)
+            }
+        }
+    }
+}
+
+/// Lets intervals be used with the standard library, for instance
+/// `BTreeMap::range()` or `Vec::drain()`.
+///
+/// # Panics
+///
+/// An empty interval has no value that could be used for its bounds, so
+/// these functions panic for it.  Check [`Interval::is_empty()`] first, or
+/// use [`Interval::bounds()`] which returns None for an empty interval.
+/// ```
+/// #  use rust_intervals::interval;
+///    let mut v = vec![0, 1, 2, 3, 4, 5];
+///    let removed = v.drain(interval!(1, 3, "[]")).collect::<Vec<_>>();
+///    assert_eq!(removed, vec![1, 2, 3]);
+///    assert_eq!(v, vec![0, 4, 5]);
+/// ```
+impl<T> RangeBounds<T> for Interval<T> {
+    fn start_bound(&self) -> RgBound<&T> {
+        match &self.lower {
+            Bound::LeftUnbounded => RgBound::Unbounded,
+            Bound::LeftOf(lo) => RgBound::Included(lo),
+            Bound::RightOf(lo) => RgBound::Excluded(lo),
+            Bound::RightUnbounded => panic!("empty intervals have no bounds"),
+        }
+    }
+    fn end_bound(&self) -> RgBound<&T> {
+        match &self.upper {
+            Bound::LeftUnbounded => panic!("empty intervals have no bounds"),
+            Bound::LeftOf(up) => RgBound::Excluded(up),
+            Bound::RightOf(up) => RgBound::Included(up),
+            Bound::RightUnbounded => RgBound::Unbounded,
+        }
+    }
+}
+
+/// Same as for `Interval<T>`, to avoid cloning the interval.
+/// ```
+/// #  use rust_intervals::interval;
+///    use std::collections::BTreeMap;
+///    let map = BTreeMap::from([(1, "a"), (5, "b"), (10, "c")]);
+///    let intv1 = interval!(1, 10, "()");
+///    assert_eq!(map.range(&intv1).collect::<Vec<_>>(), vec![(&5, &"b")]);
+/// ```
+impl<T> RangeBounds<T> for &Interval<T> {
+    fn start_bound(&self) -> RgBound<&T> {
+        (*self).start_bound()
+    }
+    fn end_bound(&self) -> RgBound<&T> {
+        (*self).end_bound()
+    }
+}
+
 impl<T: Clone> ::core::convert::From<::core::ops::RangeFull> for Interval<T> {
     fn from(_: ::core::ops::RangeFull) -> Self {
         Interval::doubly_unbounded()
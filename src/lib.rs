@@ -19,11 +19,21 @@
 //! Any type can be used for the bounds, though operations on the interval
 //! depends on the traits that the bound type implements.
 //!
+//! The bounds of an interval are available as a [`Bound`] (see
+//! [`Interval::lower_bound()`] and [`Interval::from_bounds()`]), or as the
+//! standard `core::ops::Bound` (see [`Interval::bounds()`],
+//! [`Interval::into_bounds()`] and the conversion from a pair of bounds with
+//! `Interval::from`).  Intervals implement `RangeBounds`, so they can be
+//! passed to the standard collections, for instance `BTreeMap::range()`;
+//! this panics for empty intervals, which have no bounds.
+//!
 //! Intervals on floats (like any code using float) can be tricky.  For
 //! instance, the two intervals `[1.0, 100.0)` and `[1.0, 100.0 - f32:EPSILON]`
 //! are not considered equivalent, since the machine thinks the two upper
 //! bounds have the same value, but one of them is closed and the other is
 //! open.
+//! Use [`Rigorous`] instead of `f64` when the floats should be considered
+//! as real numbers, and results of operations must enclose the exact result.
 //!
 //! Although this type is mostly intended to be used when T can be ordered,
 //! it is in fact possible to define intervals using any type.  But only a few
@@ -64,13 +74,23 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+mod allen;
+mod arithmetic;
 mod bounds;
 mod intervals;
 mod iterator;
+mod leftmostiter;
+mod measure;
+mod notation;
 mod nothing_between;
 mod pairs;
+mod postgres;
+mod rigorous;
+mod split;
 mod step;
+mod streaming;
 mod tests;
+mod translate;
 
 #[cfg(feature = "serde")]
 mod serde;
@@ -81,26 +101,60 @@ mod chrono;
 #[cfg(feature = "rust_decimal")]
 mod decimal;
 
-pub use crate::intervals::{Interval, ParseError};
+pub use crate::allen::{AllenRelation, AllenRelations};
+pub use crate::arithmetic::Arithmetic;
+pub use crate::bounds::Bound;
+pub use crate::intervals::{BoundsError, Interval, ParseError, ParseErrorAt};
 pub use crate::iterator::IntervalIterator;
+pub use crate::measure::Measure;
+pub use crate::notation::{Notation, NotationDisplay};
 pub use crate::nothing_between::NothingBetween;
 pub use crate::pairs::Pair;
+pub use crate::postgres::Postgres;
+pub use crate::rigorous::Rigorous;
+pub use crate::split::SplitSide;
 pub use crate::step::{Bounded, Step};
+pub use crate::streaming::{
+    coalesce, difference_iter, intersection_iter, union_iter, Coalesce,
+};
+pub use crate::translate::Translate;
 
 #[cfg(feature = "std")]
-mod leftmostiter;
+mod map;
 #[cfg(feature = "std")]
 mod multi;
 #[cfg(feature = "std")]
+mod multi_btree;
+#[cfg(feature = "std")]
+mod multi_canonical;
+#[cfg(feature = "std")]
 mod multi_joining;
 #[cfg(feature = "std")]
 mod multi_separating;
 #[cfg(feature = "std")]
-pub use crate::multi::IntervalSet;
+mod multi_splitting;
+#[cfg(feature = "std")]
+mod tree;
+#[cfg(feature = "std")]
+pub use crate::map::IntervalMap;
+#[cfg(feature = "std")]
+pub use crate::multi::{IntervalSet, Policy};
+#[cfg(feature = "std")]
+pub use crate::multi_btree::{
+    BTreeIntervalSet, BTreeIntervalSetIter, SortedIntervals,
+};
+#[cfg(feature = "std")]
+pub use crate::multi_canonical::Canonical;
 #[cfg(feature = "std")]
 pub use crate::multi_joining::Joining;
 #[cfg(feature = "std")]
 pub use crate::multi_separating::Separating;
+#[cfg(feature = "std")]
+pub use crate::multi_splitting::Splitting;
+#[cfg(feature = "std")]
+pub use crate::streaming::merge_all;
+#[cfg(feature = "std")]
+pub use crate::tree::IntervalTree;
 
 /// This macro lets you create intervals with a syntax closer to what Postgresql
 /// provides.
@@ -0,0 +1,289 @@
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use crate::pairs::Pair;
+
+/// A sorted list of non-overlapping intervals, each associated with a value.
+///
+/// Inserting an interval overrides the values for the part of the existing
+/// intervals it overlaps.  Touching or overlapping intervals are joined when
+/// their associated values are equal, similar to what the [`crate::Joining`]
+/// policy does for sets.
+/// ```none
+///    {[1------3):a          }
+///  +       [2------4):b
+///  +                 [4-5):b
+///  = {[1--2):a[2--------5):b}
+/// ```
+#[derive(Debug)]
+pub struct IntervalMap<T, V> {
+    entries: Vec<(Interval<T>, V)>,
+}
+
+impl<T, V> IntervalMap<T, V> {
+    /// Returns an empty map
+    #[must_use]
+    pub fn empty() -> Self {
+        IntervalMap {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Create a map from a collection of intervals and their values.  Those
+    /// intervals do not have to be sorted.  When they overlap, the intervals
+    /// inserted last override the values of the previous ones.
+    pub fn new<I>(iter: I) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        V: PartialEq + Clone,
+        I: IntoIterator<Item = (Interval<T>, V)>,
+    {
+        let mut m = IntervalMap::empty();
+        m.extend(iter);
+        m
+    }
+
+    /// Return the number of intervals in self.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True if there are no intervals in self
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all intervals from the map
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Associate value with all values of intv.  This overrides the
+    /// previous value for the parts of existing intervals that overlap
+    /// intv.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalMap};
+    ///    let mut map = IntervalMap::empty();
+    ///    map.insert(interval!(1, 10), 'a');
+    ///    map.insert(interval!(4, 6), 'b');
+    ///    assert_eq!(map.get(3), Some(&'a'));
+    ///    assert_eq!(map.get(4), Some(&'b'));
+    ///    assert_eq!(map.get(6), Some(&'a'));
+    ///    assert_eq!(map.len(), 3);
+    /// ```
+    pub fn insert(&mut self, intv: Interval<T>, value: V)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        V: PartialEq + Clone,
+    {
+        if intv.is_empty() {
+            return;
+        }
+        let idx = self.replace(&intv, Some(value));
+        self.join_with_next(idx);
+        if idx > 0 {
+            self.join_with_next(idx - 1);
+        }
+    }
+
+    /// Join the interval at idx with the next one, if they are contiguous
+    /// and have the same value.
+    fn join_with_next(&mut self, idx: usize)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        V: PartialEq,
+    {
+        let joined = match (self.entries.get(idx), self.entries.get(idx + 1)) {
+            (Some((e, v)), Some((next, next_v))) if v == next_v => {
+                e.union(next)
+            }
+            _ => None,
+        };
+        if let Some(u) = joined {
+            self.entries.remove(idx + 1);
+            if let Some(e) = self.entries.get_mut(idx) {
+                e.0 = u;
+            }
+        }
+    }
+
+    /// Remove from self all values found in intv.  Existing intervals might
+    /// be split in two, in which case both parts keep the same value.
+    pub fn remove<U>(&mut self, intv: U)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        V: Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let u = intv.borrow();
+        if u.is_empty() {
+            return;
+        }
+
+        self.replace(u, None);
+    }
+
+    /// Removes all values of intv from self, and associates them with value
+    /// instead, if one is given.  Only the entries that overlap intv are
+    /// modified, so this is O(log n) plus the number of those entries (and
+    /// the cost of shifting the entries on their right).
+    /// Returns the index of the new entry.
+    fn replace(&mut self, intv: &Interval<T>, value: Option<V>) -> usize
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        V: Clone,
+    {
+        // At most the first and last overlapping entries keep a part that is
+        // outside of intv, on its left and its right respectively.
+        let (start, end) = self.overlapping_range(intv);
+        let mut pieces = Vec::with_capacity(3);
+        for (e, v) in self.entries.get(start..end).unwrap_or(&[]) {
+            match e.difference(intv) {
+                Pair::One(p1) => {
+                    if !p1.is_empty() {
+                        pieces.push((p1, v.clone()));
+                    }
+                }
+                Pair::Two(p1, p2) => {
+                    pieces.push((p1, v.clone()));
+                    pieces.push((p2, v.clone()));
+                }
+            }
+        }
+        let idx = start
+            + pieces
+                .iter()
+                .filter(|(p, _)| p.strictly_left_of_interval(intv))
+                .count();
+        if let Some(value) = value {
+            pieces.insert(idx - start, (intv.clone(), value));
+        }
+        self.entries.splice(start..end, pieces);
+        idx
+    }
+
+    /// The range of indexes for the entries that overlap intv
+    fn overlapping_range(&self, intv: &Interval<T>) -> (usize, usize)
+    where
+        T: PartialOrd + NothingBetween,
+    {
+        let start = self
+            .entries
+            .partition_point(|(e, _)| e.strictly_left_of_interval(intv));
+        let end = self
+            .entries
+            .partition_point(|(e, _)| !intv.strictly_left_of_interval(e));
+        (start, end)
+    }
+
+    /// Return the value associated with the interval that contains value,
+    /// if any.
+    pub fn get<K>(&self, value: K) -> Option<&V>
+    where
+        T: PartialOrd + NothingBetween,
+        K: ::core::borrow::Borrow<T>,
+    {
+        self.get_key_value(value).map(|(_, v)| v)
+    }
+
+    /// Return the interval that contains value, along with its associated
+    /// value.
+    pub fn get_key_value<K>(&self, value: K) -> Option<(&Interval<T>, &V)>
+    where
+        T: PartialOrd + NothingBetween,
+        K: ::core::borrow::Borrow<T>,
+    {
+        let t = value.borrow();
+        let idx = self.entries.partition_point(|(e, _)| e.strictly_left_of(t));
+        match self.entries.get(idx) {
+            Some((e, v)) if e.contains(t) => Some((e, v)),
+            _ => None,
+        }
+    }
+
+    /// Whether value is contained in any of the intervals
+    pub fn contains_key<K>(&self, value: K) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        K: ::core::borrow::Borrow<T>,
+    {
+        self.get_key_value(value).is_some()
+    }
+
+    /// Iterate over all intervals and their values, from left to right.
+    pub fn iter(&self) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        self.entries.iter().map(|(e, v)| (e, v))
+    }
+
+    /// Iterate over all intervals that have at least one value in common
+    /// with intv, and their associated values.  The intervals are returned
+    /// unchanged, so might extend beyond intv.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalMap};
+    ///    let map = IntervalMap::new([
+    ///        (interval!(1, 3), 'a'),
+    ///        (interval!(5, 8), 'b'),
+    ///        (interval!(10, 12), 'c'),
+    ///    ]);
+    ///    assert_eq!(
+    ///        map.range(interval!(2, 6)).collect::<Vec<_>>(),
+    ///        [(&interval!(1, 3), &'a'), (&interval!(5, 8), &'b')],
+    ///    );
+    /// ```
+    pub fn range<U>(&self, intv: U) -> impl Iterator<Item = (&Interval<T>, &V)>
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let (start, end) = self.overlapping_range(intv.borrow());
+        self.entries
+            .get(start..end)
+            .unwrap_or(&[])
+            .iter()
+            .map(|(e, v)| (e, v))
+    }
+}
+
+impl<T, V> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        IntervalMap::empty()
+    }
+}
+
+impl<T, V> ::core::clone::Clone for IntervalMap<T, V>
+where
+    T: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+
+impl<T, V> Extend<(Interval<T>, V)> for IntervalMap<T, V>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    V: PartialEq + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Interval<T>, V)>,
+    {
+        for (intv, value) in iter {
+            self.insert(intv, value);
+        }
+    }
+}
+
+impl<T, V> PartialEq for IntervalMap<T, V>
+where
+    T: PartialOrd + NothingBetween,
+    V: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
@@ -0,0 +1,147 @@
+/// Computes the length of intervals.
+///
+/// For discrete types, the length is the number of values in the interval
+/// (or the number of nanoseconds for durations), so that equal intervals
+/// like `[1, 5]` and `[1, 6)` have the same length.  Continuous types only
+/// consider the difference between the bounds.
+/// ```
+/// #  use rust_intervals::{interval, Interval};
+///    assert_eq!(interval!(1, 5, "[]").length(), Some(5_u32));
+///    assert_eq!(interval!(1, 6, "[)").length(), Some(5_u32));
+///    assert_eq!(interval!(1.0, 5.0, "[]").length(), Some(4.0));
+///    assert_eq!(interval!(1, "inf").length(), None);
+/// ```
+pub trait Measure {
+    /// The type of lengths, for instance an unsigned integer for integers
+    /// or a duration for dates.  The default value is the zero length.
+    type Output: Default;
+
+    /// The length of the interval from self to upper, where `self <= upper`
+    /// and the interval is not empty.  `inclusive` tells whether each of the
+    /// two bounds is part of the interval.
+    /// Returns None if the length cannot be represented.
+    fn measure(
+        &self,
+        upper: &Self,
+        inclusive: (bool, bool),
+    ) -> Option<Self::Output>;
+
+    /// Adds two lengths.  Returns None on overflow.
+    fn add_lengths(
+        length1: &Self::Output,
+        length2: &Self::Output,
+    ) -> Option<Self::Output>;
+
+    /// Returns `length * numerator / denominator`, where
+    /// `numerator < denominator`.  This is used to split intervals into
+    /// parts of the same length, see [`Interval::partition_by_length()`].
+    /// The default implementation returns None, for types whose lengths
+    /// cannot be divided.
+    ///
+    /// [`Interval::partition_by_length()`]: crate::Interval::partition_by_length
+    fn fraction(
+        _length: &Self::Output,
+        _numerator: u32,
+        _denominator: u32,
+    ) -> Option<Self::Output> {
+        None
+    }
+}
+
+macro_rules! measure_for_int {
+    ($t:tt, $output:tt) => {
+        impl Measure for $t {
+            type Output = $output;
+            fn measure(
+                &self,
+                upper: &Self,
+                inclusive: (bool, bool),
+            ) -> Option<Self::Output> {
+                let mut length = upper.abs_diff(*self);
+                if !inclusive.0 {
+                    length = length.checked_sub(1)?;
+                }
+                if inclusive.1 {
+                    length = length.checked_add(1)?;
+                }
+                Some(length)
+            }
+            fn add_lengths(
+                length1: &Self::Output,
+                length2: &Self::Output,
+            ) -> Option<Self::Output> {
+                length1.checked_add(*length2)
+            }
+        }
+    };
+}
+
+macro_rules! measure_for_float {
+    ($t:tt) => {
+        impl Measure for $t {
+            type Output = $t;
+            fn measure(
+                &self,
+                upper: &Self,
+                _inclusive: (bool, bool),
+            ) -> Option<Self::Output> {
+                Some(upper - self)
+            }
+            fn add_lengths(
+                length1: &Self::Output,
+                length2: &Self::Output,
+            ) -> Option<Self::Output> {
+                Some(length1 + length2)
+            }
+            fn fraction(
+                length: &Self::Output,
+                numerator: u32,
+                denominator: u32,
+            ) -> Option<Self::Output> {
+                Some(length / denominator as $t * numerator as $t)
+                    .filter(|r| r.is_finite())
+            }
+        }
+    };
+}
+
+measure_for_int!(u8, u8);
+measure_for_int!(u16, u16);
+measure_for_int!(u32, u32);
+measure_for_int!(u64, u64);
+measure_for_int!(u128, u128);
+measure_for_int!(i8, u8);
+measure_for_int!(i16, u16);
+measure_for_int!(i32, u32);
+measure_for_int!(i64, u64);
+measure_for_int!(i128, u128);
+measure_for_int!(usize, usize);
+measure_for_int!(isize, usize);
+measure_for_float!(f32);
+measure_for_float!(f64);
+
+#[cfg(feature = "std")]
+impl Measure for std::time::Duration {
+    type Output = std::time::Duration;
+    fn measure(
+        &self,
+        upper: &Self,
+        inclusive: (bool, bool),
+    ) -> Option<Self::Output> {
+        let nano = std::time::Duration::from_nanos(1);
+        let mut length = upper.checked_sub(*self)?;
+        if !inclusive.0 {
+            length = length.checked_sub(nano)?;
+        }
+        if inclusive.1 {
+            length = length.checked_add(nano)?;
+        }
+        Some(length)
+    }
+    fn add_lengths(
+        length1: &Self::Output,
+        length2: &Self::Output,
+    ) -> Option<Self::Output> {
+        length1.checked_add(*length2)
+    }
+}
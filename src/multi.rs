@@ -1,8 +1,17 @@
-use crate::intervals::Interval;
+use crate::arithmetic::Arithmetic;
+use crate::intervals::{Interval, ParseError, ParseErrorAt};
+use crate::leftmostiter::LeftMostIter;
+use crate::measure::Measure;
+use crate::multi_btree::SortedIntervals;
+use crate::multi_canonical::Canonical;
 use crate::multi_joining::Joining;
 use crate::multi_separating::Separating;
+use crate::multi_splitting::Splitting;
 use crate::nothing_between::NothingBetween;
 use crate::pairs::Pair;
+use crate::postgres::{parse_multirange, Postgres};
+use crate::step::{Bounded, Step};
+use crate::translate::Translate;
 use ::core::cmp::Ordering;
 use ::core::marker::PhantomData;
 
@@ -13,6 +22,14 @@ pub trait Policy<T> {
     fn merge(vec: &mut Vec<Interval<T>>, elements: Vec<Interval<T>>)
     where
         T: PartialOrd + NothingBetween + Clone;
+
+    /// Called by the operations that compute intervals without going
+    /// through merge(), like `difference_interval()` or `complement()`, on
+    /// the intervals they create.  Those intervals are sorted, not empty,
+    /// and already combined as the policy requires.  This must not change
+    /// the values they contain.
+    /// The default implementation does nothing.
+    fn normalize(_vec: &mut [Interval<T>]) {}
 }
 
 /// A sorted list of non-overlapping intervals.
@@ -47,6 +64,9 @@ pub trait Policy<T> {
 ///      +                 [4-5)
 ///      = {[1-2)[2-3)[3-4)[4-5)}
 ///     ```
+///
+/// For discrete types, any of those policies can be wrapped in
+/// [`Canonical`], so that intervals are stored in their closed-open form.
 #[derive(Debug)]
 pub struct IntervalSet<T, P: Policy<T> = Joining> {
     intvs: Vec<Interval<T>>,
@@ -97,6 +117,53 @@ impl<T> IntervalSet<T, Separating> {
     }
 }
 
+impl<T> IntervalSet<T, Splitting> {
+    #[must_use]
+    pub fn empty_splitting() -> Self {
+        Default::default()
+    }
+
+    pub fn new_splitting<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_splitting(value: T) -> Self
+    where
+        T: PartialOrd + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
+impl<T> IntervalSet<T, Canonical>
+where
+    T: Step + Clone,
+{
+    #[must_use]
+    pub fn empty_canonical() -> Self {
+        Default::default()
+    }
+
+    pub fn new_canonical<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_canonical(value: T) -> Self
+    where
+        T: PartialOrd + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
 impl<T, P: Policy<T>> IntervalSet<T, P> {
     /// Returns an empty multi interval
     /// ```none
@@ -118,10 +185,7 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
     where
         T: PartialOrd + Clone,
     {
-        IntervalSet {
-            intvs: vec![Interval::new_single(value)],
-            _policy: PhantomData,
-        }
+        IntervalSet::from_sorted(vec![Interval::new_single(value)])
     }
 
     /// Create a multi-interval from a collection of intervals.
@@ -144,15 +208,40 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
     where
         T: PartialOrd + NothingBetween,
     {
+        IntervalSet::from_sorted(match pair {
+            Pair::One(intv) => vec![intv],
+            Pair::Two(intv1, intv2) => vec![intv1, intv2],
+        })
+    }
+
+    /// Create a multi-interval from intervals that are sorted, not empty,
+    /// and already combined as the policy requires.
+    fn from_sorted(mut intvs: Vec<Interval<T>>) -> Self {
+        P::normalize(&mut intvs);
         IntervalSet {
-            intvs: match pair {
-                Pair::One(intv) => vec![intv],
-                Pair::Two(intv1, intv2) => vec![intv1, intv2],
-            },
+            intvs,
             _policy: PhantomData,
         }
     }
 
+    /// Create a multi-interval from a collection of intervals, which do not
+    /// have to be sorted or non-overlapping.  Contrary to [`IntervalSet::new`],
+    /// this doesn't require T to be `Ord`.
+    pub(crate) fn from_unsorted(mut elements: Vec<Interval<T>>) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        let mut result = IntervalSet::empty();
+        elements.retain(|intv| !intv.is_empty());
+        if !elements.is_empty() {
+            elements.sort_by(|i1, i2| {
+                i1.partial_cmp(i2).unwrap_or(Ordering::Equal)
+            });
+            P::merge(&mut result.intvs, elements);
+        }
+        result
+    }
+
     /// Return the lowest valid value amongst all the intervals, or None if
     /// self is empty or if the left-most interval is unbounded.
     /// This value might not actually be valid for self, if we have an
@@ -297,6 +386,7 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
             }
         }
         result.intvs.retain(|v| !v.is_empty());
+        P::normalize(&mut result.intvs);
         result
     }
 
@@ -315,19 +405,25 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         U: ::core::borrow::Borrow<Interval<T>>,
     {
         let u = intv.borrow();
-        for (idx, v) in self.intvs.iter_mut().enumerate() {
-            match v.difference(u) {
-                Pair::One(p1) => {
-                    *v = p1;
-                }
-                Pair::Two(p1, p2) => {
-                    *v = p1;
-                    self.intvs.insert(idx + 1, p2);
-                    break;
+        let (start, end) = self.overlapping_range(u);
+        let mut pieces = Vec::new();
+        if let Some(overlap) = self.intvs.get(start..end) {
+            for v in overlap {
+                match v.difference(u) {
+                    Pair::One(p1) => {
+                        if !p1.is_empty() {
+                            pieces.push(p1);
+                        }
+                    }
+                    Pair::Two(p1, p2) => {
+                        pieces.push(p1);
+                        pieces.push(p2);
+                    }
                 }
             }
+            P::normalize(&mut pieces);
+            self.intvs.splice(start..end, pieces);
         }
-        self.intvs.retain(|v| !v.is_empty());
     }
 
     /// Iterate over all intervals
@@ -335,6 +431,83 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         self.intvs.iter()
     }
 
+    /// The sum of the lengths of all intervals, see [`Interval::length()`].
+    /// Returns None if the set is unbounded, or if the total cannot be
+    /// represented.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(set.total_length(), Some(5_u32));
+    /// ```
+    #[must_use]
+    pub fn total_length(&self) -> Option<T::Output>
+    where
+        T: Measure,
+    {
+        total_length(self.intvs.iter())
+    }
+
+    /// The number of values in the set, see [`Interval::cardinality()`].
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(set.cardinality(), Some(5));
+    /// ```
+    #[must_use]
+    pub fn cardinality(&self) -> Option<u128>
+    where
+        T: Step + Bounded + PartialOrd + NothingBetween,
+    {
+        cardinality(self.intvs.iter())
+    }
+
+    /// Displays the set using the PostgreSQL text format for multiranges.
+    /// See [`Interval::postgres()`].
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(set.postgres().to_string(), "{[1,3),[5,8)}");
+    ///    assert_eq!(IntervalSet::<u32>::empty().postgres().to_string(), "{}");
+    /// ```
+    #[must_use]
+    pub fn postgres(&self) -> Postgres<'_, Self> {
+        Postgres(self)
+    }
+
+    /// Parses a set written with the PostgreSQL text format for
+    /// multiranges, as output by [`IntervalSet::postgres()`], for instance
+    /// `{[1,3), empty, [5,)}` or `{}`.  Each interval is parsed as for
+    /// [`Interval::parse_postgres()`].
+    /// The intervals do not have to be sorted, and might overlap.  They are
+    /// combined as per the policy.
+    /// Errors report the index of the invalid interval, if any, as well as
+    /// the position in the input.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet, ParseError};
+    /// #  fn main() -> Result<(), ParseError<::core::num::ParseIntError>> {
+    ///    let set = IntervalSet::<u32>::parse_postgres("{[5,8), [1,3)}")?;
+    ///    assert_eq!(set, IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]));
+    ///
+    ///    let err = IntervalSet::<u32>::parse_postgres("{[1,3), [a,8)}");
+    ///    assert!(matches!(
+    ///        err.as_ref().map_err(|e| e.error()),
+    ///        Err(ParseError::MemberBound(1, _)),
+    ///    ));
+    ///    assert_eq!(err.map_err(|e| e.offset()), Err(9));
+    /// #  Ok(())
+    /// #  }
+    /// ```
+    pub fn parse_postgres<E>(s: &str) -> Result<Self, ParseErrorAt<E>>
+    where
+        T: PartialOrd
+            + Bounded
+            + NothingBetween
+            + Clone
+            + ::core::str::FromStr<Err = E>,
+    {
+        Ok(IntervalSet::from_unsorted(parse_multirange(s)?))
+    }
+
     /// Whether the two sets contain the same set of values
     pub fn equivalent<U>(&self, other: U) -> bool
     where
@@ -345,21 +518,75 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         self.iter().eq(u.iter())
     }
 
-    /// Whether value is valid for any of the intervals in self
+    /// Whether value is valid for any of the intervals in self.
+    /// This runs in logarithmic time.
     pub fn contains<V>(&self, value: V) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        V: ::core::borrow::Borrow<T>,
+    {
+        self.find(value).is_some()
+    }
+
+    /// Returns the interval that contains value, if any.
+    /// This runs in logarithmic time.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///    ]);
+    ///    assert_eq!(set.find(12), Some(&interval!(10, 20)));
+    ///    assert_eq!(set.find(7), None);
+    /// ```
+    pub fn find<V>(&self, value: V) -> Option<&Interval<T>>
     where
         T: PartialOrd + NothingBetween,
         V: ::core::borrow::Borrow<T>,
     {
         let t = value.borrow();
-        for intv in self.iter() {
-            if !intv.lower.left_of(t) {
-                return false;
-            } else if intv.upper.right_of(t) {
-                return true;
-            }
-        }
-        false
+        let idx = self.intvs.partition_point(|v| v.strictly_left_of(t));
+        self.intvs.get(idx).filter(|v| v.contains(t))
+    }
+
+    /// Returns the intervals of self that have at least one value in common
+    /// with intv.  The intervals are returned unchanged, so might extend
+    /// beyond intv.
+    /// This runs in logarithmic time.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///        interval!(30, 40),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.overlapping(interval!(4, 12)),
+    ///        [interval!(1, 5), interval!(10, 20)],
+    ///    );
+    /// ```
+    pub fn overlapping<U>(&self, intv: U) -> &[Interval<T>]
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let (start, end) = self.overlapping_range(intv.borrow());
+        self.intvs.get(start..end).unwrap_or(&[])
+    }
+
+    /// The range of indexes for the intervals that intersect intv.  If
+    /// intv is empty, start might be greater than end.
+    fn overlapping_range(&self, intv: &Interval<T>) -> (usize, usize)
+    where
+        T: PartialOrd + NothingBetween,
+    {
+        let start = self
+            .intvs
+            .partition_point(|v| v.strictly_left_of_interval(intv));
+        let end = self
+            .intvs
+            .partition_point(|v| !intv.strictly_left_of_interval(v));
+        (start, end)
     }
 
     /// Whether all values in other are valid for self.
@@ -448,27 +675,30 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         T: PartialOrd + NothingBetween + Clone,
         U: ::core::borrow::Borrow<Interval<T>>,
     {
-        let mut result = IntervalSet::empty();
         let u = intv.borrow();
-        if u.is_empty() {
-            return result;
-        }
-
-        for v in &self.intvs {
-            if u.upper < v.lower {
-                break;
-            }
-
-            let inters = v.intersection(u);
-            if !inters.is_empty() {
-                result.intvs.push(inters);
-            }
-        }
-        return result;
+        IntervalSet::from_sorted(
+            self.overlapping(u)
+                .iter()
+                .map(|v| v.intersection(u))
+                .collect(),
+        )
     }
 
     /// Returns the intersection of self and intv.
     /// This could return any number of intervals.
+    /// This runs in linear time, walking both sets in parallel.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set1 = IntervalSet::new_joining([interval!(1, 10)]);
+    ///    let set2 = IntervalSet::new_joining([interval!(5, 20)]);
+    ///    let inters = set1.intersection_set(&set2);
+    ///    assert_eq!(inters, IntervalSet::new_joining([interval!(5, 10)]));
+    ///
+    ///    assert_eq!(&set1 & &set2, inters);
+    ///    assert_eq!(&set1 & set2.clone(), inters);
+    ///    assert_eq!(set1.clone() & &set2, inters);
+    ///    assert_eq!(set1 & set2, inters);
+    /// ```
     pub fn intersection_set<U, P2>(&self, intv: U) -> Self
     where
         P2: Policy<T>,
@@ -476,21 +706,366 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         U: ::core::borrow::Borrow<IntervalSet<T, P2>>,
     {
         let mut result = IntervalSet::empty();
+        let elements = intersect_sorted(&self.intvs, &intv.borrow().intvs);
+
+        // The two sets might use different policies, so the intervals need
+        // to be joined or split again.
+        P::merge(&mut result.intvs, elements);
+        result
+    }
+
+    /// Returns the union of self and intv, i.e. all values that are in
+    /// either of them.
+    pub fn union_interval<U>(&self, intv: U) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut result = self.clone();
+        result.add(intv.borrow().clone());
+        result
+    }
+
+    /// Returns the union of self and intv, i.e. all values that are in
+    /// either of the sets.
+    /// This runs in linear time (except for the [`Splitting`] policy),
+    /// walking both sets in parallel.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set1 = IntervalSet::new_joining([interval!(1, 10)]);
+    ///    let set2 = IntervalSet::new_joining([interval!(5, 20)]);
+    ///    let union = set1.union_set(&set2);
+    ///    assert_eq!(union, IntervalSet::new_joining([interval!(1, 20)]));
+    ///
+    ///    assert_eq!(&set1 | &set2, union);
+    ///    assert_eq!(&set1 | set2.clone(), union);
+    ///    assert_eq!(set1.clone() | &set2, union);
+    ///    assert_eq!(set1 | set2, union);
+    /// ```
+    pub fn union_set<U, P2>(&self, intv: U) -> Self
+    where
+        P2: Policy<T>,
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<IntervalSet<T, P2>>,
+    {
+        let mut result = self.clone();
+        result.union_set_in_place(intv.borrow());
+        result
+    }
+
+    /// Returns a set of intervals that includes all values of self except
+    /// those found in intv.
+    /// This runs in linear time, walking both sets in parallel.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set1 = IntervalSet::new_joining([interval!(1, 10)]);
+    ///    let set2 = IntervalSet::new_joining([interval!(5, 20)]);
+    ///    let diff = set1.difference_set(&set2);
+    ///    assert_eq!(diff, IntervalSet::new_joining([interval!(1, 5)]));
+    ///
+    ///    assert_eq!(&set1 - &set2, diff);
+    ///    assert_eq!(&set1 - set2.clone(), diff);
+    ///    assert_eq!(set1.clone() - &set2, diff);
+    ///    assert_eq!(set1 - set2, diff);
+    /// ```
+    pub fn difference_set<U, P2>(&self, intv: U) -> Self
+    where
+        P2: Policy<T>,
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<IntervalSet<T, P2>>,
+    {
+        let mut result = self.clone();
+        result.difference_set_in_place(intv.borrow());
+        result
+    }
+
+    /// Returns the values that are either in self or in intv, but not in
+    /// both.
+    pub fn symmetric_difference_interval<U>(&self, intv: U) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut other = IntervalSet::<T, P>::empty();
+        other.add(intv.borrow().clone());
+        self.symmetric_difference_set(other)
+    }
+
+    /// Returns the values that are either in self or in intv, but not in
+    /// both.
+    /// This runs in linear time (except for the [`Splitting`] policy).
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set1 = IntervalSet::new_joining([interval!(1, 10)]);
+    ///    let set2 = IntervalSet::new_joining([interval!(5, 20)]);
+    ///    let symdiff = set1.symmetric_difference_set(&set2);
+    ///    assert_eq!(
+    ///        symdiff,
+    ///        IntervalSet::new_joining([interval!(1, 5), interval!(10, 20)]),
+    ///    );
+    ///
+    ///    assert_eq!(&set1 ^ &set2, symdiff);
+    ///    assert_eq!(&set1 ^ set2.clone(), symdiff);
+    ///    assert_eq!(set1.clone() ^ &set2, symdiff);
+    ///    assert_eq!(set1 ^ set2, symdiff);
+    /// ```
+    pub fn symmetric_difference_set<U, P2>(&self, intv: U) -> Self
+    where
+        P2: Policy<T>,
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<IntervalSet<T, P2>>,
+    {
         let u = intv.borrow();
-        if u.is_empty() {
-            return result;
-        }
+        self.difference_set(u).union_set(u.difference_set(self))
+    }
 
-        for v in &self.intvs {
-            let inters = u.intersection_interval(v);
-            if !inters.is_empty() {
-                result.intvs.extend(inters.intvs);
-            }
-            if u.left_of_interval(v) {
-                break;
+    /// Iterate over the holes of self, i.e. the values between two
+    /// consecutive intervals.  Contiguous intervals (as can be found with
+    /// the [`Separating`] policy) have no hole between them.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///        interval!(30, 40),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.gaps().collect::<Vec<_>>(),
+    ///        [interval!(5, 10), interval!(20, 30)],
+    ///    );
+    /// ```
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        self.intvs
+            .iter()
+            .zip(self.intvs.iter().skip(1))
+            .map(|(i1, i2)| i1.between(i2))
+            .filter(|gap| !gap.is_empty())
+    }
+
+    /// Iterate over the holes of self that are within window.  This
+    /// includes the leading and trailing holes, between the bounds of
+    /// window and the first and last intervals of self.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///        interval!(30, 40),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.gaps_within(&interval!(0, 25)).collect::<Vec<_>>(),
+    ///        [interval!(0, 1), interval!(5, 10), interval!(20, 25)],
+    ///    );
+    /// ```
+    pub fn gaps_within<'a>(
+        &'a self,
+        window: &'a Interval<T>,
+    ) -> impl Iterator<Item = Interval<T>> + 'a
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        let members = self.overlapping(window);
+        let uppers = ::core::iter::once(&window.lower)
+            .chain(members.iter().map(|v| &v.upper));
+        let lowers = members
+            .iter()
+            .map(|v| &v.lower)
+            .chain(::core::iter::once(&window.upper));
+        uppers
+            .zip(lowers)
+            .map(|(up, lo)| Interval::from_bounds(up, lo))
+            .filter(|gap| !gap.is_empty())
+    }
+
+    /// Returns all values that are not in self, i.e. the gaps between the
+    /// intervals as well as the unbounded ends.  The [`Bounded`] trait is
+    /// used so that, for instance, the complement of `[0, 255]` for `u8` is
+    /// empty.
+    /// This is the same as the [`!`] operator.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.complement(),
+    ///        IntervalSet::new_joining([
+    ///            interval!("-inf", 1, ")"),
+    ///            interval!(5, 10),
+    ///            interval!(20, "[inf"),
+    ///        ]),
+    ///    );
+    ///    assert_eq!(!&set, set.complement());
+    ///    assert_eq!(!!set.clone(), set);
+    /// ```
+    #[must_use]
+    pub fn complement(&self) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone + Bounded,
+    {
+        let mut result = IntervalSet::empty();
+        match (self.intvs.first(), self.intvs.last()) {
+            (Some(first), Some(last)) => {
+                let before = Interval::left_of_bound(&first.lower);
+                if !before.is_empty() {
+                    result.intvs.push(before);
+                }
+                result.intvs.extend(self.gaps());
+                let after = Interval::right_of_bound(&last.upper);
+                if !after.is_empty() {
+                    result.intvs.push(after);
+                }
             }
+            _ => result.intvs.push(Interval::doubly_unbounded()),
         }
-        return result;
+        P::normalize(&mut result.intvs);
+        result
+    }
+
+    /// Returns all values of domain that are not in self.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.complement_within(interval!(0, 15)),
+    ///        IntervalSet::new_joining([interval!(0, 1), interval!(5, 10)]),
+    ///    );
+    /// ```
+    pub fn complement_within<U>(&self, domain: U) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut result = IntervalSet::empty();
+        result.add(domain.borrow().clone());
+        result.difference_set(self)
+    }
+
+    /// Applies an increasing function to the bounds of all intervals, see
+    /// [`Interval::map()`].  The intervals are combined again according to
+    /// the policy, in case f made some of them overlap or touch.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(
+    ///        set.map(|v| v * 10),
+    ///        IntervalSet::new_joining([interval!(10, 30), interval!(50, 80)]),
+    ///    );
+    /// ```
+    pub fn map<U, F>(&self, mut f: F) -> IntervalSet<U, P>
+    where
+        U: PartialOrd + NothingBetween + Clone,
+        P: Policy<U>,
+        F: FnMut(&T) -> U,
+    {
+        IntervalSet::from_unsorted(
+            self.intvs.iter().map(|intv| intv.map(&mut f)).collect(),
+        )
+    }
+
+    /// Applies a decreasing function to the bounds of all intervals, see
+    /// [`Interval::map_decreasing()`].
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(
+    ///        set.map_decreasing(|v| -v),
+    ///        IntervalSet::new_joining([
+    ///            interval!(-8, -5, "(]"),
+    ///            interval!(-3, -1, "(]"),
+    ///        ]),
+    ///    );
+    /// ```
+    pub fn map_decreasing<U, F>(&self, mut f: F) -> IntervalSet<U, P>
+    where
+        U: PartialOrd + NothingBetween + Clone,
+        P: Policy<U>,
+        F: FnMut(&T) -> U,
+    {
+        IntervalSet::from_unsorted(
+            self.intvs
+                .iter()
+                .rev()
+                .map(|intv| intv.map_decreasing(&mut f))
+                .collect(),
+        )
+    }
+
+    /// Moves all intervals by delta, see [`Interval::shift()`].
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(
+    ///        set.shift(&10),
+    ///        Some(IntervalSet::new_joining([
+    ///            interval!(11, 13),
+    ///            interval!(15, 18),
+    ///        ])),
+    ///    );
+    /// ```
+    pub fn shift<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.shift(delta))
+    }
+
+    /// Widens all intervals by delta on both sides, see
+    /// [`Interval::expand()`].  Intervals that start overlapping are
+    /// combined according to the policy.
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(
+    ///        set.expand(&1),
+    ///        Some(IntervalSet::new_joining([interval!(0, 9)])),
+    ///    );
+    /// ```
+    pub fn expand<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.expand(delta))
+    }
+
+    /// Narrows all intervals by delta on both sides, see
+    /// [`Interval::shrink()`].  Intervals that become empty are removed.
+    /// Returns None on overflow.
+    pub fn shrink<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.shrink(delta))
+    }
+
+    /// Scales all intervals around pivot, see [`Interval::scale_around()`].
+    /// Returns None on overflow.
+    pub fn scale_around(&self, pivot: &T, factor: &T) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+    {
+        self.try_map_intervals(|intv| intv.scale_around(pivot, factor))
+    }
+
+    /// Applies f to all intervals, and combines the results according to
+    /// the policy.  Returns None if f returns None for any interval.
+    fn try_map_intervals<F>(&self, f: F) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        F: FnMut(&Interval<T>) -> Option<Interval<T>>,
+    {
+        Some(IntervalSet::from_unsorted(
+            self.intvs.iter().map(f).collect::<Option<Vec<_>>>()?,
+        ))
     }
 
     /// Returns the convex hull, i.e. the smallest intervals that contains
@@ -514,8 +1089,7 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
         T: PartialOrd + NothingBetween,
         U: ::core::borrow::Borrow<Interval<T>>,
     {
-        let u = right.borrow();
-        self.iter().any(|v| v.intersects(u))
+        !self.overlapping(right).is_empty()
     }
 
     /// Whether any value exists in both self and right.
@@ -650,6 +1224,18 @@ impl<T, P: Policy<T>> IntervalSet<T, P> {
     }
 }
 
+impl<T, P: Policy<T>> SortedIntervals<T> for IntervalSet<T, P> {
+    type Iter<'a>
+        = ::core::slice::Iter<'a, Interval<T>>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn sorted_iter(&self) -> Self::Iter<'_> {
+        self.intvs.iter()
+    }
+}
+
 impl<T, P: Policy<T>> Default for IntervalSet<T, P> {
     fn default() -> Self {
         IntervalSet::empty()
@@ -689,62 +1275,548 @@ where
     }
 }
 
-///   &IntervalSet - &Interval
-///   and &IntervalSet - Interval
-impl<T, U, P: Policy<T>> ::core::ops::Sub<U> for &IntervalSet<T, P>
+/// In-place versions of the set operations, used by the assignment
+/// operators so that self is not cloned.
+impl<T, P: Policy<T>> IntervalSet<T, P>
 where
     T: PartialOrd + NothingBetween + Clone,
-    U: ::core::borrow::Borrow<Interval<T>>,
 {
-    type Output = IntervalSet<T, P>;
+    fn union_interval_in_place(&mut self, intv: &Interval<T>) {
+        self.add(intv.clone());
+    }
 
-    /// Same as [`IntervalSet::remove_interval()`]
-    fn sub(self, rhs: U) -> Self::Output {
-        self.difference_interval(rhs)
+    fn union_set_in_place<P2: Policy<T>>(
+        &mut self,
+        other: &IntervalSet<T, P2>,
+    ) {
+        let old = ::core::mem::take(&mut self.intvs);
+        let elements =
+            LeftMostIter::new(old.into_iter(), other.intvs.iter().cloned())
+                .collect::<Vec<_>>();
+        P::merge(&mut self.intvs, elements);
+    }
+
+    fn intersection_interval_in_place(&mut self, intv: &Interval<T>) {
+        let (start, end) = self.overlapping_range(intv);
+        self.intvs.truncate(end);
+        self.intvs.drain(..start);
+        for v in self.intvs.iter_mut() {
+            *v = v.intersection(intv);
+        }
+        P::normalize(&mut self.intvs);
+    }
+
+    fn intersection_set_in_place<P2: Policy<T>>(
+        &mut self,
+        other: &IntervalSet<T, P2>,
+    ) {
+        let elements = intersect_sorted(&self.intvs, &other.intvs);
+        self.intvs.clear();
+        P::merge(&mut self.intvs, elements);
+    }
+
+    fn difference_interval_in_place(&mut self, intv: &Interval<T>) {
+        self.remove_interval(intv);
+    }
+
+    fn difference_set_in_place<P2: Policy<T>>(
+        &mut self,
+        other: &IntervalSet<T, P2>,
+    ) {
+        let old = ::core::mem::take(&mut self.intvs);
+        let mut right = other.intvs.iter().peekable();
+
+        for mut rest in old {
+            while let Some(r) = right.peek() {
+                if r.strictly_left_of_interval(&rest) {
+                    right.next();
+                } else if rest.strictly_left_of_interval(*r) {
+                    break;
+                } else {
+                    match rest.difference(*r) {
+                        Pair::One(p1) => rest = p1,
+                        Pair::Two(p1, p2) => {
+                            self.intvs.push(p1);
+                            rest = p2;
+                        }
+                    }
+                    if rest.is_empty() {
+                        break;
+                    }
+                }
+            }
+            if !rest.is_empty() {
+                self.intvs.push(rest);
+            }
+        }
+        P::normalize(&mut self.intvs);
+    }
+
+    fn symmetric_difference_interval_in_place(&mut self, intv: &Interval<T>) {
+        let mut other = IntervalSet::<T, P>::empty();
+        other.add(intv.clone());
+        self.symmetric_difference_set_in_place(&other);
+    }
+
+    fn symmetric_difference_set_in_place<P2: Policy<T>>(
+        &mut self,
+        other: &IntervalSet<T, P2>,
+    ) {
+        let only_other = other.difference_set(&*self);
+        self.difference_set_in_place(other);
+        self.union_set_in_place(&only_other);
     }
 }
 
-///   IntervalSet - &Interval
-///   and IntervalSet - Interval
-impl<T, U, P: Policy<T>> ::core::ops::Sub<U> for IntervalSet<T, P>
+/// The intersections of two sorted lists of non-overlapping intervals.
+/// This runs in linear time, walking both lists in parallel.
+fn intersect_sorted<T>(
+    left: &[Interval<T>],
+    right: &[Interval<T>],
+) -> Vec<Interval<T>>
 where
     T: PartialOrd + NothingBetween + Clone,
-    U: ::core::borrow::Borrow<Interval<T>>,
 {
-    type Output = IntervalSet<T, P>;
+    let mut elements = Vec::new();
+    let mut left = left.iter().peekable();
+    let mut right = right.iter().peekable();
+
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let inters = l.intersection(*r);
+        if !inters.is_empty() {
+            elements.push(inters);
+        }
 
-    /// Same as [`IntervalSet::remove_interval()`]
-    fn sub(self, rhs: U) -> Self::Output {
-        self.difference_interval(rhs)
+        // Move forward the interval that ends first, the other one might
+        // still intersect with the next intervals.
+        if l.upper <= r.upper {
+            left.next();
+        } else {
+            right.next();
+        }
     }
+    elements
 }
 
-impl<T, U, P: Policy<T>> ::core::ops::SubAssign<U> for IntervalSet<T, P>
+/// Implements a binary operator for all combinations of references and
+/// values for a set of intervals ($set is either IntervalSet or
+/// BTreeIntervalSet), with either an Interval or a set of the same kind as
+/// the right operand.  The set must provide the methods named in the
+/// parameters, and T must satisfy $bounds.
+macro_rules! set_operator {
+    ($set:ident, [$($bounds:tt)+],
+     $trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident,
+     $for_interval:ident, $for_set:ident,
+     $in_place_interval:ident, $in_place_set:ident) => {
+        ///   &Set op Interval
+        impl<T, P: Policy<T>> ::core::ops::$trait<Interval<T>>
+            for &$set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(self, rhs: Interval<T>) -> Self::Output {
+                self.$for_interval(rhs)
+            }
+        }
+
+        ///   &Set op &Interval
+        impl<T, P: Policy<T>> ::core::ops::$trait<&Interval<T>>
+            for &$set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(self, rhs: &Interval<T>) -> Self::Output {
+                self.$for_interval(rhs)
+            }
+        }
+
+        ///   Set op Interval
+        impl<T, P: Policy<T>> ::core::ops::$trait<Interval<T>>
+            for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(mut self, rhs: Interval<T>) -> Self::Output {
+                self.$in_place_interval(&rhs);
+                self
+            }
+        }
+
+        ///   Set op &Interval
+        impl<T, P: Policy<T>> ::core::ops::$trait<&Interval<T>>
+            for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(mut self, rhs: &Interval<T>) -> Self::Output {
+                self.$in_place_interval(rhs);
+                self
+            }
+        }
+
+        ///   &Set op Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$trait<$set<T, P2>> for &$set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(self, rhs: $set<T, P2>) -> Self::Output {
+                self.$for_set(rhs)
+            }
+        }
+
+        ///   &Set op &Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$trait<&$set<T, P2>> for &$set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(self, rhs: &$set<T, P2>) -> Self::Output {
+                self.$for_set(rhs)
+            }
+        }
+
+        ///   Set op Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$trait<$set<T, P2>> for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(mut self, rhs: $set<T, P2>) -> Self::Output {
+                self.$in_place_set(&rhs);
+                self
+            }
+        }
+
+        ///   Set op &Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$trait<&$set<T, P2>> for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            type Output = $set<T, P>;
+
+            fn $method(mut self, rhs: &$set<T, P2>) -> Self::Output {
+                self.$in_place_set(rhs);
+                self
+            }
+        }
+
+        ///   Set op= Interval
+        impl<T, P: Policy<T>> ::core::ops::$assign_trait<Interval<T>>
+            for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            fn $assign_method(&mut self, rhs: Interval<T>) {
+                self.$in_place_interval(&rhs);
+            }
+        }
+
+        ///   Set op= &Interval
+        impl<T, P: Policy<T>> ::core::ops::$assign_trait<&Interval<T>>
+            for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            fn $assign_method(&mut self, rhs: &Interval<T>) {
+                self.$in_place_interval(rhs);
+            }
+        }
+
+        ///   Set op= Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$assign_trait<$set<T, P2>> for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            fn $assign_method(&mut self, rhs: $set<T, P2>) {
+                self.$in_place_set(&rhs);
+            }
+        }
+
+        ///   Set op= &Set
+        impl<T, P: Policy<T>, P2: Policy<T>>
+            ::core::ops::$assign_trait<&$set<T, P2>>
+            for $set<T, P>
+        where
+            T: $($bounds)+,
+        {
+            fn $assign_method(&mut self, rhs: &$set<T, P2>) {
+                self.$in_place_set(rhs);
+            }
+        }
+    };
+}
+
+pub(crate) use set_operator;
+
+/// Implements all the set operators, including Not, for $set
+macro_rules! set_operators {
+    ($set:ident, [$($bounds:tt)+]) => {
+        $crate::multi::set_operator!(
+            $set,
+            [$($bounds)+],
+            BitOr,
+            bitor,
+            BitOrAssign,
+            bitor_assign,
+            union_interval,
+            union_set,
+            union_interval_in_place,
+            union_set_in_place
+        );
+        $crate::multi::set_operator!(
+            $set,
+            [$($bounds)+],
+            BitAnd,
+            bitand,
+            BitAndAssign,
+            bitand_assign,
+            intersection_interval,
+            intersection_set,
+            intersection_interval_in_place,
+            intersection_set_in_place
+        );
+        $crate::multi::set_operator!(
+            $set,
+            [$($bounds)+],
+            BitXor,
+            bitxor,
+            BitXorAssign,
+            bitxor_assign,
+            symmetric_difference_interval,
+            symmetric_difference_set,
+            symmetric_difference_interval_in_place,
+            symmetric_difference_set_in_place
+        );
+        $crate::multi::set_operator!(
+            $set,
+            [$($bounds)+],
+            Sub,
+            sub,
+            SubAssign,
+            sub_assign,
+            difference_interval,
+            difference_set,
+            difference_interval_in_place,
+            difference_set_in_place
+        );
+
+        ///   !&Set
+        impl<T, P: Policy<T>> ::core::ops::Not for &$set<T, P>
+        where
+            T: $($bounds)+ + Bounded,
+        {
+            type Output = $set<T, P>;
+
+            /// Same as `complement()`
+            fn not(self) -> Self::Output {
+                self.complement()
+            }
+        }
+
+        ///   !Set
+        impl<T, P: Policy<T>> ::core::ops::Not for $set<T, P>
+        where
+            T: $($bounds)+ + Bounded,
+        {
+            type Output = $set<T, P>;
+
+            /// Same as `complement()`
+            fn not(self) -> Self::Output {
+                self.complement()
+            }
+        }
+    };
+}
+pub(crate) use set_operators;
+
+set_operators!(IntervalSet, [PartialOrd + NothingBetween + Clone]);
+
+impl<T, P: Policy<T>> ::core::fmt::Display for IntervalSet<T, P>
 where
-    T: PartialOrd + NothingBetween + Clone,
-    U: ::core::borrow::Borrow<Interval<T>>,
+    T: ::core::fmt::Display + NothingBetween + PartialOrd,
 {
-    fn sub_assign(&mut self, rhs: U) {
-        self.remove_interval(rhs);
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        fmt_set(f, self.intvs.iter())
     }
 }
 
-impl<T> ::core::fmt::Display for IntervalSet<T>
+impl<T, P: Policy<T>> ::core::fmt::Display for Postgres<'_, IntervalSet<T, P>>
 where
-    T: ::core::fmt::Display + NothingBetween + PartialOrd,
+    T: ::core::fmt::Display,
 {
     fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-        if self.is_empty() {
-            write!(f, "empty")
-        } else {
-            write!(f, "{{")?;
-            for (idx, v) in self.iter().enumerate() {
-                if idx != 0 {
-                    write!(f, ", ")?;
+        fmt_postgres_set(f, self.0.intvs.iter())
+    }
+}
+
+impl<T, P: Policy<T>, E> ::core::str::FromStr for IntervalSet<T, P>
+where
+    T: PartialOrd
+        + Bounded
+        + NothingBetween
+        + Clone
+        + ::core::str::FromStr<Err = E>,
+{
+    type Err = ParseError<E>;
+
+    /// Parses the output of Display, for instance `{[1, 3), (5, 7], [9,)}`
+    /// or `empty`.
+    /// The intervals do not have to be sorted, and might overlap.  They are
+    /// combined as per the policy.
+    /// As for intervals, it is assumed that the bounds do not contain any
+    /// of the characters `,`, `]` or `)`.  See
+    /// [`IntervalSet::parse_postgres()`] for bounds that contain them.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(IntervalSet::from_unsorted(parse_set(s)?))
+    }
+}
+
+impl<T, P: Policy<T>, E> ::core::convert::TryFrom<&str> for IntervalSet<T, P>
+where
+    T: PartialOrd
+        + Bounded
+        + NothingBetween
+        + Clone
+        + ::core::str::FromStr<Err = E>,
+    E: ::core::fmt::Debug,
+{
+    type Error = ParseError<E>;
+
+    /// Convert from a string to a set of intervals.
+    /// The format of the string is similar to what Display provides.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// The sum of the lengths of the intervals, see
+/// [`IntervalSet::total_length()`].
+pub(crate) fn total_length<'a, T>(
+    mut intvs: impl Iterator<Item = &'a Interval<T>>,
+) -> Option<T::Output>
+where
+    T: Measure + 'a,
+{
+    intvs.try_fold(T::Output::default(), |total, intv| {
+        T::add_lengths(&total, &intv.length()?)
+    })
+}
+
+/// The number of values in the intervals, see
+/// [`IntervalSet::cardinality()`].
+pub(crate) fn cardinality<'a, T>(
+    mut intvs: impl Iterator<Item = &'a Interval<T>>,
+) -> Option<u128>
+where
+    T: Step + Bounded + PartialOrd + NothingBetween + 'a,
+{
+    intvs.try_fold(0_u128, |total, intv| total.checked_add(intv.cardinality()?))
+}
+
+/// Displays sorted intervals as a set, for instance `{[1, 3), [5, 7)}`
+pub(crate) fn fmt_set<'a, T>(
+    f: &mut ::core::fmt::Formatter<'_>,
+    intvs: impl Iterator<Item = &'a Interval<T>>,
+) -> ::core::fmt::Result
+where
+    T: ::core::fmt::Display + NothingBetween + PartialOrd + 'a,
+{
+    let mut intvs = intvs.peekable();
+    if intvs.peek().is_none() {
+        return write!(f, "empty");
+    }
+    write!(f, "{{")?;
+    for (idx, v) in intvs.enumerate() {
+        if idx != 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", v)?;
+    }
+    write!(f, "}}")
+}
+
+/// Displays sorted intervals as a PostgreSQL multirange
+pub(crate) fn fmt_postgres_set<'a, T>(
+    f: &mut ::core::fmt::Formatter<'_>,
+    intvs: impl Iterator<Item = &'a Interval<T>>,
+) -> ::core::fmt::Result
+where
+    T: ::core::fmt::Display + 'a,
+{
+    write!(f, "{{")?;
+    for (idx, v) in intvs.enumerate() {
+        if idx != 0 {
+            write!(f, ",")?;
+        }
+        write!(f, "{}", v.postgres())?;
+    }
+    write!(f, "}}")
+}
+
+/// Parses the output of Display for a set of intervals, and returns the
+/// intervals in the order they appear, see the implementation of `FromStr`
+/// for [`IntervalSet`].
+pub(crate) fn parse_set<T, E>(
+    s: &str,
+) -> Result<Vec<Interval<T>>, ParseError<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let s = s.trim();
+    if s == "empty" {
+        return Ok(Vec::new());
+    }
+    let inner = s
+        .strip_prefix('{')
+        .and_then(|s| s.strip_suffix('}'))
+        .ok_or(ParseError::InvalidInput)?;
+
+    let mut elements = Vec::new();
+    let mut start_offset: Option<usize> = None;
+    for (c_offset, c) in inner.char_indices() {
+        match start_offset {
+            None => {
+                if c == '[' || c == '(' {
+                    start_offset = Some(c_offset);
+                } else if c != ',' && !c.is_whitespace() {
+                    return Err(ParseError::InvalidInput);
+                }
+            }
+            Some(offs) => {
+                if c == ']' || c == ')' {
+                    let index = elements.len();
+                    let member = inner
+                        .get(offs..=c_offset)
+                        .ok_or(ParseError::InvalidMember(index))?;
+                    elements.push(
+                        member
+                            .parse::<Interval<T>>()
+                            .map_err(|e| e.for_member(index))?,
+                    );
+                    start_offset = None;
                 }
-                write!(f, "{}", v)?;
             }
-            write!(f, "}}")
         }
     }
+    if start_offset.is_some() {
+        return Err(ParseError::InvalidMember(elements.len()));
+    }
+    Ok(elements)
 }
@@ -0,0 +1,1204 @@
+use crate::arithmetic::Arithmetic;
+use crate::bounds::Bound;
+use crate::intervals::Interval;
+use crate::intervals::{ParseError, ParseErrorAt};
+use crate::leftmostiter::LeftMostIter;
+use crate::measure::Measure;
+use crate::multi::{
+    cardinality, fmt_postgres_set, fmt_set, parse_set, set_operators,
+    total_length, IntervalSet, Policy,
+};
+use crate::multi_canonical::Canonical;
+use crate::multi_joining::Joining;
+use crate::multi_separating::Separating;
+use crate::multi_splitting::Splitting;
+use crate::nothing_between::NothingBetween;
+use crate::pairs::Pair;
+use crate::postgres::{parse_multirange, Postgres};
+use crate::step::{Bounded, Step};
+use crate::translate::Translate;
+use ::core::cmp::Ordering;
+use ::core::marker::PhantomData;
+use ::std::collections::{btree_map, BTreeMap};
+
+/// A sorted list of non-overlapping intervals, similar to [`IntervalSet`]
+/// and supporting the same policies.
+///
+/// The intervals are stored in a balanced tree rather than a vector, so that
+/// adding or removing intervals anywhere in the set runs in logarithmic time
+/// (plus the number of intervals that need to be merged or split).
+/// On the other hand, iteration and lookups are slower than for
+/// [`IntervalSet`], so the latter should be preferred when the set is mostly
+/// read.
+///
+/// Because a tree needs a total order, T must implement `Ord`.
+///
+/// Set operations accept either a `BTreeIntervalSet` or an [`IntervalSet`]
+/// (see [`SortedIntervals`]), with any policy.
+/// ```
+/// #  use rust_intervals::{interval, BTreeIntervalSet};
+///    let mut set = BTreeIntervalSet::empty_joining();
+///    set.add(interval!(1, 5));
+///    set.add(interval!(10, 20));
+///    set.add(interval!(4, 12));
+///    set.remove_interval(interval!(6, 8));
+///    assert_eq!(
+///        set.iter().collect::<Vec<_>>(),
+///        [&interval!(1, 6), &interval!(8, 20)],
+///    );
+/// ```
+#[derive(Debug)]
+pub struct BTreeIntervalSet<T, P: Policy<T> = Joining> {
+    // Intervals, indexed by their lower bound
+    intvs: BTreeMap<Bound<T>, Interval<T>>,
+    _policy: PhantomData<P>,
+}
+
+impl<T> BTreeIntervalSet<T, Joining> {
+    #[must_use]
+    pub fn empty_joining() -> Self {
+        Default::default()
+    }
+
+    pub fn new_joining<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_joining(value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
+impl<T> BTreeIntervalSet<T, Separating> {
+    #[must_use]
+    pub fn empty_separating() -> Self {
+        Default::default()
+    }
+
+    pub fn new_separating<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_separating(value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
+impl<T> BTreeIntervalSet<T, Splitting> {
+    #[must_use]
+    pub fn empty_splitting() -> Self {
+        Default::default()
+    }
+
+    pub fn new_splitting<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_splitting(value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
+impl<T> BTreeIntervalSet<T, Canonical>
+where
+    T: Step + Clone,
+{
+    #[must_use]
+    pub fn empty_canonical() -> Self {
+        Default::default()
+    }
+
+    pub fn new_canonical<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        Self::new(iter)
+    }
+
+    pub fn new_single_canonical(value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        Self::new_single(value)
+    }
+}
+
+impl<T, P: Policy<T>> BTreeIntervalSet<T, P> {
+    /// Returns an empty multi interval
+    #[must_use]
+    pub fn empty() -> Self {
+        BTreeIntervalSet {
+            intvs: BTreeMap::new(),
+            _policy: PhantomData,
+        }
+    }
+
+    /// Create a multi-interval that contains a single value
+    pub fn new_single(value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        let mut m = BTreeIntervalSet::empty();
+        m.add(Interval::new_single(value));
+        m
+    }
+
+    /// Create a multi-interval from a collection of intervals.
+    /// Those intervals do not have to be sorted, or non-overlapping
+    pub fn new<I>(iter: I) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        let mut m = BTreeIntervalSet::empty();
+        m.extend(iter);
+        m
+    }
+
+    /// Create a multi-interval from a pair (returned by
+    /// `Interval::difference()` for instance).
+    /// It is assumed that the intervals in pair do not overlap.
+    pub fn from_pair(pair: Pair<T>) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        let mut m = BTreeIntervalSet::empty();
+        match pair {
+            Pair::One(intv) => m.insert_disjoint(intv),
+            Pair::Two(intv1, intv2) => {
+                m.insert_disjoint(intv1);
+                m.insert_disjoint(intv2);
+            }
+        }
+        m
+    }
+
+    /// Return the lowest valid value amongst all the intervals, or None if
+    /// self is empty or if the left-most interval is unbounded.
+    #[must_use]
+    pub fn lower(&self) -> Option<&T> {
+        self.iter().next().and_then(Interval::lower)
+    }
+
+    /// True if the left-most interval is unbounded.
+    /// This is false if self is empty.
+    #[must_use]
+    pub fn lower_unbounded(&self) -> bool {
+        self.iter().next().map_or(false, Interval::lower_unbounded)
+    }
+
+    /// Return the highest valid value amongst all the intervals, or None if
+    /// self is empty or if the right-most interval is unbounded.
+    #[must_use]
+    pub fn upper(&self) -> Option<&T> {
+        self.iter().next_back().and_then(Interval::upper)
+    }
+
+    /// True if the right-most interval is unbounded.
+    /// This is false if self is empty.
+    #[must_use]
+    pub fn upper_unbounded(&self) -> bool {
+        self.iter()
+            .next_back()
+            .map_or(false, Interval::upper_unbounded)
+    }
+
+    /// Return the number of intervals in self.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.intvs.len()
+    }
+
+    /// True if there are not values in self
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.intvs.is_empty()
+    }
+
+    /// Removes all intervals from the set
+    pub fn clear(&mut self) {
+        self.intvs.clear();
+    }
+
+    /// Add an extra set of valid values to self.
+    /// Only the intervals that overlap or touch intv need to be merged, so
+    /// this runs in logarithmic time.
+    pub fn add(&mut self, intv: Interval<T>)
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        if intv.is_empty() {
+            return;
+        }
+
+        let touching = self
+            .intvs
+            .range(..=intv.upper.clone())
+            .rev()
+            .take_while(|(_, v)| !v.strictly_left_not_contiguous(&intv))
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>();
+
+        let mut elements = Vec::with_capacity(touching.len() + 1);
+        for k in touching.iter().rev() {
+            if let Some(v) = self.intvs.remove(k) {
+                elements.push(v);
+            }
+        }
+        let pos = elements.partition_point(|v| *v < intv);
+        elements.insert(pos, intv);
+
+        let mut merged = Vec::new();
+        P::merge(&mut merged, elements);
+        for v in merged {
+            self.intvs.insert(v.lower.clone(), v);
+        }
+    }
+
+    /// Add multiple sets of valid values to self, via an iterator.
+    pub fn extend<I>(&mut self, iter: I)
+    where
+        T: Ord + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        for intv in iter {
+            self.add(intv);
+        }
+    }
+
+    /// Return a set of intervals that includes all values of self except
+    /// value.
+    pub fn difference(&self, value: T) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        self.difference_interval(Interval::new_single(value))
+    }
+
+    /// Returns a set of intervals that includes all values of self except
+    /// those found in intv.
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet};
+    ///    let set1 = BTreeIntervalSet::new_joining([interval!(1, 20)]);
+    ///    let intv1 = interval!(5, 10);
+    ///    let diff = set1.difference_interval(&intv1);
+    ///    assert_eq!(
+    ///        diff,
+    ///        BTreeIntervalSet::new_joining([interval!(1, 5), interval!(10, 20)]),
+    ///    );
+    ///    assert_eq!(&set1 - &intv1, diff);
+    ///    assert_eq!(set1 - intv1, diff);
+    /// ```
+    pub fn difference_interval<U>(&self, intv: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut result = self.clone();
+        result.remove_interval(intv);
+        result
+    }
+
+    /// Remove value from self
+    pub fn remove(&mut self, value: T)
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        self.remove_interval(Interval::new_single(value));
+    }
+
+    /// Remove all values in intv from self.
+    /// This runs in logarithmic time (plus the number of intervals that
+    /// overlap intv).
+    pub fn remove_interval<U>(&mut self, intv: U)
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let u = intv.borrow();
+        let overlapping = self
+            .intvs
+            .range(..u.upper.clone())
+            .rev()
+            .take_while(|(_, v)| !v.strictly_left_of_interval(u))
+            .map(|(k, _)| k.clone())
+            .collect::<Vec<_>>();
+
+        for k in overlapping {
+            if let Some(v) = self.intvs.remove(&k) {
+                match v.difference(u) {
+                    Pair::One(p1) => self.insert_disjoint(p1),
+                    Pair::Two(p1, p2) => {
+                        self.insert_disjoint(p1);
+                        self.insert_disjoint(p2);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Insert an interval known not to overlap any interval in self.
+    /// Empty intervals are ignored.
+    fn insert_disjoint(&mut self, mut intv: Interval<T>)
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        if !intv.is_empty() {
+            P::normalize(::core::slice::from_mut(&mut intv));
+            self.intvs.insert(intv.lower.clone(), intv);
+        }
+    }
+
+    /// Iterate over all intervals
+    #[must_use]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Interval<T>> {
+        self.intvs.values()
+    }
+
+    /// Whether the two sets contain the same intervals.  other can be
+    /// either a `BTreeIntervalSet` or an [`IntervalSet`].
+    pub fn equivalent<U>(&self, other: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: SortedIntervals<T>,
+    {
+        self.iter().eq(other.sorted_iter())
+    }
+
+    /// Whether value is valid for any of the intervals in self
+    pub fn contains<V>(&self, value: V) -> bool
+    where
+        T: Ord + NothingBetween + Clone,
+        V: ::core::borrow::Borrow<T>,
+    {
+        self.find(value).is_some()
+    }
+
+    /// Returns the interval that contains value, if any.
+    pub fn find<V>(&self, value: V) -> Option<&Interval<T>>
+    where
+        T: Ord + NothingBetween + Clone,
+        V: ::core::borrow::Borrow<T>,
+    {
+        let t = value.borrow();
+        self.intvs
+            .range(..=Bound::LeftOf(t.clone()))
+            .next_back()
+            .map(|(_, v)| v)
+            .filter(|v| v.contains(t))
+    }
+
+    /// Iterate over the intervals of self that have at least one value in
+    /// common with intv.
+    pub fn overlapping<'a>(
+        &'a self,
+        intv: &Interval<T>,
+    ) -> impl Iterator<Item = &'a Interval<T>>
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        let (start, end) = if intv.is_empty() {
+            (Bound::LeftUnbounded, Bound::LeftUnbounded)
+        } else {
+            let start =
+                match self.intvs.range(..=intv.lower.clone()).next_back() {
+                    Some((k, v)) if v.intersects(intv) => k.clone(),
+                    _ => intv.lower.clone(),
+                };
+            (start, intv.upper.clone())
+        };
+        self.intvs.range(start..end).map(|(_, v)| v)
+    }
+
+    /// Whether all values in other are valid for self.
+    /// All sets always contain the empty interval.
+    pub fn contains_interval<U>(&self, other: U) -> bool
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut reminder = other.borrow().clone();
+        for v in self.overlapping(other.borrow()) {
+            if reminder.is_empty() || reminder.lower < v.lower {
+                break;
+            }
+            reminder = Interval::from_bounds(&v.upper, &reminder.upper);
+        }
+        reminder.is_empty()
+    }
+
+    /// Whether any value exists in both self and right.
+    #[doc(alias = "overlaps")]
+    pub fn intersects_interval<U>(&self, right: U) -> bool
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        self.overlapping(right.borrow()).next().is_some()
+    }
+
+    /// Whether any value exists in both self and right.
+    #[doc(alias = "overlaps")]
+    pub fn intersects_set<U>(&self, right: U) -> bool
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        right.sorted_iter().any(|v| self.intersects_interval(v))
+    }
+
+    /// Whether all values in other are also in self
+    pub fn contains_set<U>(&self, other: U) -> bool
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        other.sorted_iter().all(|v| self.contains_interval(v))
+    }
+
+    /// Returns the intersection of self and intv.
+    pub fn intersection_interval<U>(&self, intv: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let u = intv.borrow();
+        let mut result = BTreeIntervalSet::empty();
+        for v in self.overlapping(u) {
+            result.insert_disjoint(v.intersection(u));
+        }
+        result
+    }
+
+    /// Returns the union of self and intv.
+    pub fn union_interval<U>(&self, intv: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut result = self.clone();
+        result.add(intv.borrow().clone());
+        result
+    }
+
+    /// Returns the union of self and other, which can be either a
+    /// `BTreeIntervalSet` or an [`IntervalSet`].
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet, IntervalSet};
+    ///    let set1 = BTreeIntervalSet::new_joining([interval!(1, 5)]);
+    ///    let set2 = IntervalSet::new_joining([interval!(3, 8)]);
+    ///    assert_eq!(
+    ///        set1.union_set(&set2),
+    ///        BTreeIntervalSet::new_joining([interval!(1, 8)]),
+    ///    );
+    /// ```
+    pub fn union_set<U>(&self, other: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        let elements = LeftMostIter::new(
+            self.iter().cloned(),
+            other.sorted_iter().cloned(),
+        )
+        .collect::<Vec<_>>();
+        Self::from_elements(elements)
+    }
+
+    /// Returns the intersection of self and other, which can be either a
+    /// `BTreeIntervalSet` or an [`IntervalSet`].
+    pub fn intersection_set<U>(&self, other: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        let mut elements = Vec::new();
+        let mut left = self.iter().peekable();
+        let mut right = other.sorted_iter().peekable();
+
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let inters = l.intersection(*r);
+            if !inters.is_empty() {
+                elements.push(inters);
+            }
+            if l.upper <= r.upper {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+        Self::from_elements(elements)
+    }
+
+    /// Returns the values of self that are not in other, which can be
+    /// either a `BTreeIntervalSet` or an [`IntervalSet`].
+    pub fn difference_set<U>(&self, other: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        let mut result = self.clone();
+        result.difference_set_in_place(&other);
+        result
+    }
+
+    /// Returns the values that are either in self or in intv, but not in
+    /// both.
+    pub fn symmetric_difference_interval<U>(&self, intv: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut other = BTreeIntervalSet::<T, P>::empty();
+        other.add(intv.borrow().clone());
+        self.symmetric_difference_set(other)
+    }
+
+    /// Returns the values that are either in self or in other, but not in
+    /// both.  other can be either a `BTreeIntervalSet` or an
+    /// [`IntervalSet`].
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet};
+    ///    let set1 = BTreeIntervalSet::new_joining([interval!(1, 10)]);
+    ///    let set2 = BTreeIntervalSet::new_joining([interval!(5, 20)]);
+    ///    let symdiff = set1.symmetric_difference_set(&set2);
+    ///    assert_eq!(
+    ///        symdiff,
+    ///        BTreeIntervalSet::new_joining([interval!(1, 5), interval!(10, 20)]),
+    ///    );
+    ///    assert_eq!(&set1 ^ &set2, symdiff);
+    ///    assert_eq!(set1 ^ set2, symdiff);
+    /// ```
+    pub fn symmetric_difference_set<U>(&self, other: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: SortedIntervals<T>,
+    {
+        let mut only_other =
+            Self::from_elements(other.sorted_iter().cloned().collect());
+        only_other.difference_set_in_place(self);
+        self.difference_set(&other).union_set(only_other)
+    }
+
+    /// Iterate over the holes of self, i.e. the values between two
+    /// consecutive intervals.  Contiguous intervals (as can be found with
+    /// the [`Separating`] policy) have no hole between them.
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet};
+    ///    let set = BTreeIntervalSet::new_joining([
+    ///        interval!(1, 5),
+    ///        interval!(10, 20),
+    ///        interval!(30, 40),
+    ///    ]);
+    ///    assert_eq!(
+    ///        set.gaps().collect::<Vec<_>>(),
+    ///        [interval!(5, 10), interval!(20, 30)],
+    ///    );
+    /// ```
+    pub fn gaps(&self) -> impl Iterator<Item = Interval<T>> + '_
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        self.iter()
+            .zip(self.iter().skip(1))
+            .map(|(i1, i2)| i1.between(i2))
+            .filter(|gap| !gap.is_empty())
+    }
+
+    /// Iterate over the holes of self that are within window.  This
+    /// includes the leading and trailing holes, between the bounds of
+    /// window and the first and last intervals of self.
+    pub fn gaps_within<'a>(
+        &'a self,
+        window: &'a Interval<T>,
+    ) -> impl Iterator<Item = Interval<T>> + 'a
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        let uppers = ::core::iter::once(&window.lower)
+            .chain(self.overlapping(window).map(|v| &v.upper));
+        let lowers = self
+            .overlapping(window)
+            .map(|v| &v.lower)
+            .chain(::core::iter::once(&window.upper));
+        uppers
+            .zip(lowers)
+            .map(|(up, lo)| Interval::from_bounds(up, lo))
+            .filter(|gap| !gap.is_empty())
+    }
+
+    /// Returns all values that are not in self, see
+    /// [`IntervalSet::complement()`].
+    /// This is the same as the [`!`] operator.
+    #[must_use]
+    pub fn complement(&self) -> Self
+    where
+        T: Ord + NothingBetween + Clone + Bounded,
+    {
+        let mut result = BTreeIntervalSet::empty();
+        match (self.iter().next(), self.iter().next_back()) {
+            (Some(first), Some(last)) => {
+                result.insert_disjoint(Interval::left_of_bound(&first.lower));
+                for gap in self.gaps() {
+                    result.insert_disjoint(gap);
+                }
+                result.insert_disjoint(Interval::right_of_bound(&last.upper));
+            }
+            _ => result.insert_disjoint(Interval::doubly_unbounded()),
+        }
+        result
+    }
+
+    /// Returns all values of domain that are not in self.
+    pub fn complement_within<U>(&self, domain: U) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut result = BTreeIntervalSet::empty();
+        result.add(domain.borrow().clone());
+        result.difference_set_in_place(self);
+        result
+    }
+
+    /// Whether every value in self is less (<=) than right
+    /// Returns True if either set is empty.
+    pub fn left_of<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<T>,
+    {
+        match self.iter().next_back() {
+            None => true,
+            Some(l) => l.left_of(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is strictly less (<) than right
+    /// Returns True if either set is empty.
+    pub fn strictly_left_of<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<T>,
+    {
+        match self.iter().next_back() {
+            None => true,
+            Some(l) => l.strictly_left_of(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is less (<=) then all values in right.
+    /// Returns True if either set is empty.
+    pub fn left_of_interval<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        match self.iter().next_back() {
+            None => true,
+            Some(l) => l.left_of_interval(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is strictly less (<) then all values in
+    /// right.
+    /// Returns True if either set is empty.
+    pub fn strictly_left_of_interval<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        match self.iter().next_back() {
+            None => true,
+            Some(l) => l.strictly_left_of_interval(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is less then (<=) all values in right.
+    /// Returns True if either set is empty.
+    pub fn left_of_set<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: SortedIntervals<T>,
+    {
+        match right.sorted_iter().next() {
+            None => true,
+            Some(r) => self.left_of_interval(r),
+        }
+    }
+
+    /// Whether every value in self is greater or equal (>=) to right
+    /// Returns True if either set is empty.
+    pub fn right_of<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<T>,
+    {
+        match self.iter().next() {
+            None => true,
+            Some(l) => l.right_of(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is strictly greater (>) then right
+    /// Returns True if either set is empty.
+    pub fn strictly_right_of<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<T>,
+    {
+        match self.iter().next() {
+            None => true,
+            Some(l) => l.strictly_right_of(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is greater or equal (>=) than all values
+    /// in right.
+    /// Returns True if either set is empty.
+    pub fn right_of_interval<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        match self.iter().next() {
+            None => true,
+            Some(l) => l.right_of_interval(right.borrow()),
+        }
+    }
+
+    /// Whether every value in self is greater or equal (>=) than all values
+    /// in right.
+    /// Returns True if either set is empty.
+    pub fn right_of_set<U>(&self, right: U) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+        U: SortedIntervals<T>,
+    {
+        match right.sorted_iter().next_back() {
+            None => true,
+            Some(r) => self.right_of_interval(r),
+        }
+    }
+
+    /// Returns the convex hull, i.e. the smallest intervals that contains
+    /// all values in all intervals in self.
+    #[must_use]
+    pub fn convex_hull(&self) -> Interval<T>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match (self.iter().next(), self.iter().next_back()) {
+            (Some(f), Some(l)) => Interval::from_bounds(&f.lower, &l.upper),
+            _ => Interval::empty(),
+        }
+    }
+
+    /// The sum of the lengths of all intervals, see
+    /// [`IntervalSet::total_length()`].
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet};
+    ///    let set = BTreeIntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(set.total_length(), Some(5_u32));
+    /// ```
+    #[must_use]
+    pub fn total_length(&self) -> Option<T::Output>
+    where
+        T: Measure,
+    {
+        total_length(self.iter())
+    }
+
+    /// The number of values in the set, see [`IntervalSet::cardinality()`].
+    #[must_use]
+    pub fn cardinality(&self) -> Option<u128>
+    where
+        T: Step + Bounded + PartialOrd + NothingBetween,
+    {
+        cardinality(self.iter())
+    }
+
+    /// Displays the set using the PostgreSQL text format for multiranges,
+    /// see [`IntervalSet::postgres()`].
+    /// ```
+    /// #  use rust_intervals::{interval, BTreeIntervalSet};
+    ///    let set = BTreeIntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+    ///    assert_eq!(set.postgres().to_string(), "{[1,3),[5,8)}");
+    /// ```
+    #[must_use]
+    pub fn postgres(&self) -> Postgres<'_, Self> {
+        Postgres(self)
+    }
+
+    /// Parses a set written with the PostgreSQL text format for
+    /// multiranges, see [`IntervalSet::parse_postgres()`].
+    pub fn parse_postgres<E>(s: &str) -> Result<Self, ParseErrorAt<E>>
+    where
+        T: Ord
+            + Bounded
+            + NothingBetween
+            + Clone
+            + ::core::str::FromStr<Err = E>,
+    {
+        Ok(BTreeIntervalSet::from_unsorted(parse_multirange(s)?))
+    }
+
+    /// Applies an increasing function to the bounds of all intervals, see
+    /// [`IntervalSet::map()`].
+    pub fn map<U, F>(&self, mut f: F) -> BTreeIntervalSet<U, P>
+    where
+        U: Ord + NothingBetween + Clone,
+        P: Policy<U>,
+        F: FnMut(&T) -> U,
+    {
+        BTreeIntervalSet::from_unsorted(
+            self.iter().map(|intv| intv.map(&mut f)).collect(),
+        )
+    }
+
+    /// Applies a decreasing function to the bounds of all intervals, see
+    /// [`IntervalSet::map_decreasing()`].
+    pub fn map_decreasing<U, F>(&self, mut f: F) -> BTreeIntervalSet<U, P>
+    where
+        U: Ord + NothingBetween + Clone,
+        P: Policy<U>,
+        F: FnMut(&T) -> U,
+    {
+        BTreeIntervalSet::from_unsorted(
+            self.iter()
+                .rev()
+                .map(|intv| intv.map_decreasing(&mut f))
+                .collect(),
+        )
+    }
+
+    /// Moves all intervals by delta, see [`IntervalSet::shift()`].
+    /// Returns None on overflow.
+    pub fn shift<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: Ord + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.shift(delta))
+    }
+
+    /// Widens all intervals by delta on both sides, see
+    /// [`IntervalSet::expand()`].
+    /// Returns None on overflow.
+    pub fn expand<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: Ord + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.expand(delta))
+    }
+
+    /// Narrows all intervals by delta on both sides, see
+    /// [`IntervalSet::shrink()`].
+    /// Returns None on overflow.
+    pub fn shrink<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: Ord + NothingBetween + Clone + Translate<D>,
+    {
+        self.try_map_intervals(|intv| intv.shrink(delta))
+    }
+
+    /// Scales all intervals around pivot, see
+    /// [`IntervalSet::scale_around()`].
+    /// Returns None on overflow.
+    pub fn scale_around(&self, pivot: &T, factor: &T) -> Option<Self>
+    where
+        T: Ord + NothingBetween + Clone + Arithmetic,
+    {
+        self.try_map_intervals(|intv| intv.scale_around(pivot, factor))
+    }
+
+    /// Applies f to all intervals, and combines the results according to
+    /// the policy.  Returns None if f returns None for any interval.
+    fn try_map_intervals<F>(&self, f: F) -> Option<Self>
+    where
+        T: Ord + NothingBetween + Clone,
+        F: FnMut(&Interval<T>) -> Option<Interval<T>>,
+    {
+        Some(BTreeIntervalSet::from_unsorted(
+            self.iter().map(f).collect::<Option<Vec<_>>>()?,
+        ))
+    }
+
+    /// Build a set from sorted, possibly overlapping, intervals.
+    fn from_elements(elements: Vec<Interval<T>>) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        let mut merged = Vec::new();
+        if !elements.is_empty() {
+            P::merge(&mut merged, elements);
+        }
+        let mut result = BTreeIntervalSet::empty();
+        for v in merged {
+            result.insert_disjoint(v);
+        }
+        result
+    }
+
+    /// Build a set from intervals in any order, possibly empty or
+    /// overlapping.
+    pub(crate) fn from_unsorted(mut elements: Vec<Interval<T>>) -> Self
+    where
+        T: Ord + NothingBetween + Clone,
+    {
+        elements.retain(|intv| !intv.is_empty());
+        elements
+            .sort_by(|i1, i2| i1.partial_cmp(i2).unwrap_or(Ordering::Equal));
+        Self::from_elements(elements)
+    }
+}
+
+/// In-place versions of the set operations, used by the assignment
+/// operators.
+impl<T, P: Policy<T>> BTreeIntervalSet<T, P>
+where
+    T: Ord + NothingBetween + Clone,
+{
+    fn union_interval_in_place(&mut self, intv: &Interval<T>) {
+        self.add(intv.clone());
+    }
+
+    fn union_set_in_place<S: SortedIntervals<T>>(&mut self, other: &S) {
+        for v in other.sorted_iter() {
+            self.add(v.clone());
+        }
+    }
+
+    fn intersection_interval_in_place(&mut self, intv: &Interval<T>) {
+        // Remove everything on either side of intv, so that only the
+        // intervals at both ends need to be modified.
+        self.remove_interval(Interval::from_bounds(
+            &Bound::LeftUnbounded,
+            &intv.lower,
+        ));
+        self.remove_interval(Interval::from_bounds(
+            &intv.upper,
+            &Bound::RightUnbounded,
+        ));
+    }
+
+    fn intersection_set_in_place<S: SortedIntervals<T>>(&mut self, other: &S) {
+        *self = self.intersection_set(other);
+    }
+
+    fn difference_interval_in_place(&mut self, intv: &Interval<T>) {
+        self.remove_interval(intv);
+    }
+
+    fn difference_set_in_place<S: SortedIntervals<T>>(&mut self, other: &S) {
+        for v in other.sorted_iter() {
+            self.remove_interval(v);
+        }
+    }
+
+    fn symmetric_difference_interval_in_place(&mut self, intv: &Interval<T>) {
+        *self = self.symmetric_difference_interval(intv);
+    }
+
+    fn symmetric_difference_set_in_place<S: SortedIntervals<T>>(
+        &mut self,
+        other: &S,
+    ) {
+        *self = self.symmetric_difference_set(other);
+    }
+}
+
+/// Collections of sorted, non-overlapping intervals, which can be combined
+/// with a [`BTreeIntervalSet`].  This is implemented for [`IntervalSet`] and
+/// [`BTreeIntervalSet`] with any policy, and for references to them.
+pub trait SortedIntervals<T> {
+    /// The iterator returned by [`SortedIntervals::sorted_iter()`]
+    type Iter<'a>: DoubleEndedIterator<Item = &'a Interval<T>>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Iterate over the intervals, from left to right
+    fn sorted_iter(&self) -> Self::Iter<'_>;
+}
+
+/// Iterator over the intervals of a [`BTreeIntervalSet`], from left to
+/// right.
+pub struct BTreeIntervalSetIter<'a, T>(
+    btree_map::Values<'a, Bound<T>, Interval<T>>,
+);
+
+impl<'a, T> Iterator for BTreeIntervalSetIter<'a, T> {
+    type Item = &'a Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for BTreeIntervalSetIter<'_, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back()
+    }
+}
+
+impl<T, P: Policy<T>> SortedIntervals<T> for BTreeIntervalSet<T, P> {
+    type Iter<'a>
+        = BTreeIntervalSetIter<'a, T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn sorted_iter(&self) -> Self::Iter<'_> {
+        BTreeIntervalSetIter(self.intvs.values())
+    }
+}
+
+impl<T, S: SortedIntervals<T>> SortedIntervals<T> for &S {
+    type Iter<'a>
+        = S::Iter<'a>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn sorted_iter(&self) -> Self::Iter<'_> {
+        (**self).sorted_iter()
+    }
+}
+
+set_operators!(BTreeIntervalSet, [Ord + NothingBetween + Clone]);
+
+impl<T, P: Policy<T>> Default for BTreeIntervalSet<T, P> {
+    fn default() -> Self {
+        BTreeIntervalSet::empty()
+    }
+}
+
+impl<T, P: Policy<T>> ::core::clone::Clone for BTreeIntervalSet<T, P>
+where
+    T: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            intvs: self.intvs.clone(),
+            _policy: PhantomData,
+        }
+    }
+}
+
+impl<T, P: Policy<T>> Extend<Interval<T>> for BTreeIntervalSet<T, P>
+where
+    T: Ord + NothingBetween + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        BTreeIntervalSet::extend(self, iter);
+    }
+}
+
+impl<T, P: Policy<T>> PartialEq for BTreeIntervalSet<T, P>
+where
+    T: PartialOrd + NothingBetween,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T, P: Policy<T>> ::core::convert::From<IntervalSet<T, P>>
+    for BTreeIntervalSet<T, P>
+where
+    T: Ord + NothingBetween + Clone,
+{
+    fn from(value: IntervalSet<T, P>) -> Self {
+        let mut result = BTreeIntervalSet::empty();
+        for v in value.iter() {
+            result.insert_disjoint(v.clone());
+        }
+        result
+    }
+}
+
+impl<T, P: Policy<T>> ::core::convert::From<BTreeIntervalSet<T, P>>
+    for IntervalSet<T, P>
+where
+    T: Ord + NothingBetween + Clone,
+{
+    fn from(value: BTreeIntervalSet<T, P>) -> Self {
+        IntervalSet::new(value.intvs.into_values())
+    }
+}
+
+impl<T, P: Policy<T>> ::core::fmt::Display for BTreeIntervalSet<T, P>
+where
+    T: ::core::fmt::Display + NothingBetween + PartialOrd,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        fmt_set(f, self.iter())
+    }
+}
+
+impl<T, P: Policy<T>> ::core::fmt::Display
+    for Postgres<'_, BTreeIntervalSet<T, P>>
+where
+    T: ::core::fmt::Display,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        fmt_postgres_set(f, self.0.intvs.values())
+    }
+}
+
+impl<T, P: Policy<T>, E> ::core::str::FromStr for BTreeIntervalSet<T, P>
+where
+    T: Ord + Bounded + NothingBetween + Clone + ::core::str::FromStr<Err = E>,
+{
+    type Err = ParseError<E>;
+
+    /// Parses the output of Display, see the implementation of `FromStr`
+    /// for [`IntervalSet`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(BTreeIntervalSet::from_unsorted(parse_set(s)?))
+    }
+}
+
+impl<T, P: Policy<T>, E> ::core::convert::TryFrom<&str>
+    for BTreeIntervalSet<T, P>
+where
+    T: Ord + Bounded + NothingBetween + Clone + ::core::str::FromStr<Err = E>,
+    E: ::core::fmt::Debug,
+{
+    type Error = ParseError<E>;
+
+    /// Convert from a string to a set of intervals.
+    /// The format of the string is similar to what Display provides.
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
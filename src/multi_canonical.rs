@@ -0,0 +1,39 @@
+use crate::intervals::Interval;
+use crate::multi::Policy;
+use crate::multi_joining::Joining;
+use crate::nothing_between::NothingBetween;
+use crate::step::Step;
+use ::core::marker::PhantomData;
+
+/// A policy for discrete types, which stores all intervals in their
+/// canonical closed-open form (see [`Interval::canonicalize()`]), and
+/// otherwise combines them as the wrapped policy P does.
+/// As a result, sets that contain the same values are displayed, serialized
+/// and hashed identically, whatever bounds were used to insert intervals.
+///
+/// Intervals are canonicalized by all operations, including those that
+/// compute new bounds like `difference_interval()` or `complement()`.
+#[derive(Debug, Default)]
+pub struct Canonical<P = Joining>(PhantomData<P>);
+
+impl<T, P> Policy<T> for Canonical<P>
+where
+    T: Step + Clone,
+    P: Policy<T>,
+{
+    fn merge(vec: &mut Vec<Interval<T>>, elements: Vec<Interval<T>>)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        // The policies only reuse existing bounds, so the result is also
+        // canonical.
+        P::merge(vec, elements.iter().map(Interval::canonicalize).collect());
+    }
+
+    fn normalize(vec: &mut [Interval<T>]) {
+        for intv in vec.iter_mut() {
+            *intv = intv.canonicalize();
+        }
+        P::normalize(vec);
+    }
+}
@@ -7,7 +7,7 @@ use crate::nothing_between::NothingBetween;
 pub struct Joining;
 
 impl Joining {
-    fn do_merge<T, I>(vec: &mut Vec<Interval<T>>, iter: I)
+    pub(crate) fn do_merge<T, I>(vec: &mut Vec<Interval<T>>, iter: I)
     where
         T: PartialOrd + NothingBetween + Clone,
         I: IntoIterator<Item = Interval<T>>,
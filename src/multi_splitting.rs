@@ -0,0 +1,82 @@
+use crate::intervals::Interval;
+use crate::leftmostiter::LeftMostIter;
+use crate::multi::Policy;
+use crate::multi_joining::Joining;
+use crate::nothing_between::NothingBetween;
+use ::core::cmp::Ordering;
+
+#[derive(Debug, Default)]
+pub struct Splitting;
+
+impl Splitting {
+    fn do_merge<T, I>(vec: &mut Vec<Interval<T>>, iter: I)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        I: IntoIterator<Item = Interval<T>>,
+    {
+        // Every bound of every interval is preserved, so we first collect
+        // them all, then keep the pieces between two consecutive bounds that
+        // are covered by at least one of the intervals.
+        let elements = iter.into_iter().collect::<Vec<_>>();
+        let mut borders = Vec::with_capacity(elements.len() * 2);
+        for e in &elements {
+            borders.push(e.lower.clone());
+            borders.push(e.upper.clone());
+        }
+        borders.sort_by(|b1, b2| b1.partial_cmp(b2).unwrap_or(Ordering::Equal));
+        borders.dedup_by(|b1, b2| b1 == b2);
+
+        let mut union = Vec::new();
+        Joining::do_merge(&mut union, elements);
+        let mut covered = union.iter().peekable();
+
+        for (lo, up) in borders.iter().zip(borders.iter().skip(1)) {
+            let piece = Interval::from_bounds(lo, up);
+            if piece.is_empty() {
+                continue;
+            }
+            while let Some(c) = covered.peek() {
+                if c.strictly_left_of_interval(&piece) {
+                    covered.next();
+                } else {
+                    if c.contains_interval(&piece) {
+                        vec.push(piece);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl<T> Policy<T> for Splitting {
+    fn merge(vec: &mut Vec<Interval<T>>, elements: Vec<Interval<T>>)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match elements.first() {
+            None => {}
+            Some(fi) => {
+                match vec.last() {
+                    None => Self::do_merge(vec, elements),
+                    Some(la) if la.strictly_left_of_interval(fi) => {
+                        // Special case: we are inserting at the end of self.  No need to
+                        // create a new vector.
+                        Self::do_merge(vec, elements)
+                    }
+                    _ => {
+                        let mut old = Vec::new();
+                        ::core::mem::swap(vec, &mut old);
+                        Self::do_merge(
+                            vec,
+                            LeftMostIter::new(
+                                old.into_iter(),
+                                elements.into_iter(),
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,311 @@
+//! Alternative notations to display and parse intervals.
+
+use crate::bounds::Bound;
+use crate::intervals::{parse_default, Interval, ParseErrorAt};
+use crate::nothing_between::NothingBetween;
+use crate::postgres::{build_interval, parse_range};
+use crate::step::Bounded;
+
+/// The notations that can be used to display and parse intervals.
+/// ```
+/// #  use rust_intervals::{interval, Interval, Notation, ParseError};
+/// #  fn main() -> Result<(), ParseError<::core::num::ParseIntError>> {
+///    let intv = interval!(1, 5, "[)");
+///    assert_eq!(intv.display(Notation::Default).to_string(), "[1, 5)");
+///    assert_eq!(intv.display(Notation::Postgres).to_string(), "[1,5)");
+///    assert_eq!(intv.display(Notation::Rust).to_string(), "1..5");
+///    assert_eq!(intv.display(Notation::Iso31_11).to_string(), "[1, 5[");
+///    assert_eq!(
+///        intv.display(Notation::SetBuilder).to_string(),
+///        r"\{x \mid 1 \le x < 5\}",
+///    );
+///    assert_eq!(Interval::parse_with("1..5", Notation::Rust)?, intv);
+/// #  Ok(())
+/// #  }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// The notation used by `Display`: `[1, 5)`, `(1, 5]`, `(, 5]` or
+    /// `empty`
+    #[default]
+    Default,
+
+    /// The PostgreSQL text format: `[1,5)`, `(,5]` or `empty`, see
+    /// [`Interval::postgres()`] and [`Interval::parse_postgres()`]
+    Postgres,
+
+    /// Rust range syntax: `1..5`, `1..=5`, `..5`, `1..` or `..`.
+    /// Intervals with an open lower bound, as well as empty intervals, have
+    /// no equivalent and use the default notation instead.
+    Rust,
+
+    /// ISO 31-11, where open bounds use reversed brackets: `[1, 5[`,
+    /// `]1, 5]`, `]-∞, 5]` or `∅`
+    Iso31_11,
+
+    /// Set-builder notation, as written in LaTeX:
+    /// `\{x \mid 1 \le x < 5\}`, `\{x \mid -\infty < x \le 5\}` or
+    /// `\emptyset`
+    SetBuilder,
+}
+
+/// Displays an interval using a specific notation.
+/// This is returned by [`Interval::display()`].
+pub struct NotationDisplay<'a, T> {
+    pub(crate) intv: &'a Interval<T>,
+    pub(crate) notation: Notation,
+}
+
+impl<T> ::core::fmt::Display for NotationDisplay<'_, T>
+where
+    T: ::core::fmt::Display + NothingBetween + PartialOrd,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        let (lower, upper) = (&self.intv.lower, &self.intv.upper);
+        let empty = matches!(
+            (lower, upper),
+            (Bound::RightUnbounded, _) | (_, Bound::LeftUnbounded)
+        );
+        match self.notation {
+            Notation::Default => write!(f, "{}", self.intv),
+            Notation::Postgres => write!(f, "{}", self.intv.postgres()),
+            Notation::Rust => match (lower, upper) {
+                (Bound::LeftOf(l), Bound::LeftOf(u)) => {
+                    write!(f, "{}..{}", l, u)
+                }
+                (Bound::LeftOf(l), Bound::RightOf(u)) => {
+                    write!(f, "{}..={}", l, u)
+                }
+                (Bound::LeftOf(l), Bound::RightUnbounded) => {
+                    write!(f, "{}..", l)
+                }
+                (Bound::LeftUnbounded, Bound::LeftOf(u)) => {
+                    write!(f, "..{}", u)
+                }
+                (Bound::LeftUnbounded, Bound::RightOf(u)) => {
+                    write!(f, "..={}", u)
+                }
+                (Bound::LeftUnbounded, Bound::RightUnbounded) => {
+                    write!(f, "..")
+                }
+                (
+                    Bound::LeftOf(_) | Bound::LeftUnbounded,
+                    Bound::LeftUnbounded,
+                )
+                | (Bound::RightOf(_) | Bound::RightUnbounded, _) => {
+                    write!(f, "{}", self.intv)
+                }
+            },
+            Notation::Iso31_11 if empty => write!(f, "∅"),
+            Notation::Iso31_11 => {
+                match lower {
+                    Bound::LeftOf(l) => write!(f, "[{}, ", l)?,
+                    Bound::RightOf(l) => write!(f, "]{}, ", l)?,
+                    Bound::LeftUnbounded | Bound::RightUnbounded => {
+                        write!(f, "]-∞, ")?
+                    }
+                }
+                match upper {
+                    Bound::LeftOf(u) => write!(f, "{}[", u),
+                    Bound::RightOf(u) => write!(f, "{}]", u),
+                    Bound::LeftUnbounded | Bound::RightUnbounded => {
+                        write!(f, "+∞[")
+                    }
+                }
+            }
+            Notation::SetBuilder if empty => write!(f, r"\emptyset"),
+            Notation::SetBuilder => {
+                write!(f, r"\{{x \mid ")?;
+                match lower {
+                    Bound::LeftOf(l) => write!(f, r"{} \le x", l)?,
+                    Bound::RightOf(l) => write!(f, "{} < x", l)?,
+                    Bound::LeftUnbounded | Bound::RightUnbounded => {
+                        write!(f, r"-\infty < x")?
+                    }
+                }
+                match upper {
+                    Bound::LeftOf(u) => write!(f, " < {}", u)?,
+                    Bound::RightOf(u) => write!(f, r" \le {}", u)?,
+                    Bound::LeftUnbounded | Bound::RightUnbounded => {
+                        write!(f, r" < \infty")?
+                    }
+                }
+                write!(f, r"\}}")
+            }
+        }
+    }
+}
+
+/// Parse an interval written with the given notation
+pub(crate) fn parse_notation<T, E>(
+    input: &str,
+    notation: Notation,
+) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    match notation {
+        Notation::Default => parse_default(input),
+        Notation::Postgres => parse_range(input, 0),
+        Notation::Rust => parse_rust(input),
+        Notation::Iso31_11 => parse_iso(input),
+        Notation::SetBuilder => parse_set_builder(input),
+    }
+}
+
+/// Parse Rust range syntax, or the default notation for intervals that
+/// cannot be represented as a Rust range.
+fn parse_rust<T, E>(input: &str) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let (start, s) = trimmed(input, 0, input.len());
+    if s.starts_with(&['[', '('][..]) || s == "empty" {
+        return parse_default(s).map_err(|e| e.shifted(start));
+    }
+    let end = start + s.len();
+    let dots = start + s.find("..").ok_or(ParseErrorAt::invalid(end))?;
+    let (lo_offset, lo) = trimmed(input, start, dots);
+    let up_incl = s
+        .get(dots - start + 2..)
+        .map_or(false, |u| u.starts_with('='));
+    let up_start = if up_incl { dots + 3 } else { dots + 2 };
+    let (up_offset, up) = trimmed(input, up_start, end);
+    if up_incl && up.is_empty() {
+        return Err(ParseErrorAt::invalid(end));
+    }
+    Ok(build_interval(
+        parse_value(lo, lo_offset, &[])?,
+        true,
+        parse_value(up, up_offset, &[])?,
+        up_incl,
+    ))
+}
+
+/// Parse ISO 31-11 notation
+fn parse_iso<T, E>(input: &str) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let (start, s) = trimmed(input, 0, input.len());
+    if s == "∅" {
+        return Ok(Interval::empty());
+    }
+    let lo_incl = match s.chars().next() {
+        Some('[') => true,
+        Some(']') => false,
+        _ => return Err(ParseErrorAt::invalid(start)),
+    };
+    let end = start + s.len() - 1;
+    let up_incl = match s.chars().next_back() {
+        Some(']') if s.len() > 1 => true,
+        Some('[') if s.len() > 1 => false,
+        _ => return Err(ParseErrorAt::invalid(start + s.len())),
+    };
+    let comma = start + s.find(',').ok_or(ParseErrorAt::invalid(end))?;
+    let (lo_offset, lo) = trimmed(input, start + 1, comma);
+    let (up_offset, up) = trimmed(input, comma + 1, end);
+    Ok(build_interval(
+        parse_value(lo, lo_offset, &["-∞"])?,
+        lo_incl,
+        parse_value(up, up_offset, &["+∞", "∞"])?,
+        up_incl,
+    ))
+}
+
+/// Parse set-builder notation
+fn parse_set_builder<T, E>(input: &str) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let (start, s) = trimmed(input, 0, input.len());
+    if s == r"\emptyset" {
+        return Ok(Interval::empty());
+    }
+    let end = start + s.len();
+    let body = s
+        .strip_prefix(r"\{")
+        .and_then(|b| b.trim_start().strip_prefix('x'))
+        .and_then(|b| b.trim_start().strip_prefix(r"\mid"))
+        .ok_or(ParseErrorAt::invalid(start))?;
+    if !body.ends_with(r"\}") {
+        return Err(ParseErrorAt::invalid(end));
+    }
+    let body_start = end - body.len();
+    let body_end = end - 2;
+
+    // The lower bound, up to the first comparison
+    let body = input.get(body_start..body_end).unwrap_or("");
+    let (op1, op1_len, lo_incl) =
+        find_comparison(body).ok_or(ParseErrorAt::invalid(body_end))?;
+    let (lo_offset, lo) = trimmed(input, body_start, body_start + op1);
+
+    // Then the variable and the second comparison
+    let (x_offset, rest) = trimmed(input, body_start + op1 + op1_len, body_end);
+    if !rest.starts_with('x') {
+        return Err(ParseErrorAt::invalid(x_offset));
+    }
+    let (op2_offset, rest) = trimmed(input, x_offset + 1, body_end);
+    let (op2_len, up_incl) =
+        comparison_at(rest).ok_or(ParseErrorAt::invalid(op2_offset))?;
+    let (up_offset, up) = trimmed(input, op2_offset + op2_len, body_end);
+
+    Ok(build_interval(
+        parse_value(lo, lo_offset, &[r"-\infty"])?,
+        lo_incl,
+        parse_value(up, up_offset, &[r"\infty", r"+\infty"])?,
+        up_incl,
+    ))
+}
+
+/// Find the first comparison operator in s, and return its position, its
+/// length and whether it includes equality.
+fn find_comparison(s: &str) -> Option<(usize, usize, bool)> {
+    let pos = match (s.find('<'), s.find(r"\le")) {
+        (Some(lt), Some(le)) => lt.min(le),
+        (lt, le) => lt.or(le)?,
+    };
+    let (len, incl) = comparison_at(s.get(pos..)?)?;
+    Some((pos, len, incl))
+}
+
+/// If s starts with a comparison operator, return its length and whether it
+/// includes equality.
+fn comparison_at(s: &str) -> Option<(usize, bool)> {
+    if s.starts_with(r"\leq") {
+        Some((4, true))
+    } else if s.starts_with(r"\le") {
+        Some((3, true))
+    } else if s.starts_with('<') {
+        Some((1, false))
+    } else {
+        None
+    }
+}
+
+/// Returns input[start..end] without surrounding whitespace, as well as its
+/// offset in input.
+fn trimmed(input: &str, start: usize, end: usize) -> (usize, &str) {
+    let s = input.get(start..end).unwrap_or("");
+    let t = s.trim_start();
+    (start + s.len() - t.len(), t.trim_end())
+}
+
+/// Parse the value of a bound.  Returns None if the interval is unbounded on
+/// that side, i.e. the text is empty or one of the keywords for infinity.
+fn parse_value<T, E>(
+    text: &str,
+    offset: usize,
+    infinity: &[&str],
+) -> Result<Option<T>, ParseErrorAt<E>>
+where
+    T: ::core::str::FromStr<Err = E>,
+{
+    if text.is_empty() || infinity.contains(&text) {
+        Ok(None)
+    } else {
+        text.parse()
+            .map(Some)
+            .map_err(|e| ParseErrorAt::bound(offset, e))
+    }
+}
@@ -1,3 +1,5 @@
+use crate::step::Step;
+
 /// Trait to compute whether intervals are empty.
 ///
 /// For instance, for f32, we consider the numbers as representable on
@@ -39,7 +41,11 @@ macro_rules! nothing_between_for_int {
     ($t:tt) => {
         impl NothingBetween for $t {
             fn nothing_between(&self, other: &Self) -> bool {
-                other - self <= 1
+                // Avoid overflows, e.g. between i8::MIN and i8::MAX
+                match self.checked_add(1) {
+                    None => true,
+                    Some(next) => next >= *other,
+                }
             }
         }
     };
@@ -77,7 +83,10 @@ impl NothingBetween for f64 {
 }
 impl NothingBetween for char {
     fn nothing_between(&self, other: &Self) -> bool {
-        (*other as u32) - (*self as u32) <= 1
+        match self.forward(1) {
+            None => true,
+            Some(next) => next >= *other,
+        }
     }
 }
 
@@ -0,0 +1,408 @@
+//! Text format compatible with PostgreSQL ranges and multiranges.
+//!
+//! The grammar is the one described in the PostgreSQL documentation for
+//! "Range Input/Output":
+//!
+//! - whitespace is allowed before and after the range
+//! - `empty` (in any case) is the empty range
+//! - otherwise, a range is `[` or `(`, the lower bound, a comma, the upper
+//!   bound, then `]` or `)`.  A missing bound means the range is unbounded on
+//!   that side (and the bracket is then ignored).
+//! - a bound can be surrounded by double quotes, in which case it can contain
+//!   any of the special characters.  Within quotes, a doubled quote is a
+//!   literal quote.  Anywhere, a backslash escapes the next character.
+//!
+//! A multirange is a comma-separated list of ranges within braces, for
+//! instance `{[1,3), empty, [5,)}` or `{}`.
+//!
+//! As in PostgreSQL, the whitespace within the brackets is part of the
+//! bounds, so `[ 1,2)` is only valid if the type accepts leading spaces.  A
+//! bound is only unbounded when there is no character at all between the
+//! bracket and the comma.  Also as in PostgreSQL, `infinity` and `-infinity`
+//! are not keywords: they are parsed as values of the type (for instance
+//! `[1,infinity]` is a closed range for `f64`), and an error for types that
+//! do not accept them.  When printing, bounds that could be mistaken for a
+//! keyword (like the string `"infinity"` or `"empty"`) are quoted.
+//! Without the `std` feature, bounds that need unescaping (for instance
+//! `"a""b"`) cannot be parsed.
+
+use crate::bounds::Bound;
+use crate::intervals::{Interval, ParseErrorAt};
+use crate::nothing_between::NothingBetween;
+use crate::step::Bounded;
+use ::core::fmt::Write;
+
+/// Displays an interval or a set of intervals in the PostgreSQL text format.
+/// This is returned by [`Interval::postgres()`] and
+/// `IntervalSet::postgres()`.
+pub struct Postgres<'a, X: ?Sized>(pub(crate) &'a X);
+
+impl<T> ::core::fmt::Display for Postgres<'_, Interval<T>>
+where
+    T: ::core::fmt::Display,
+{
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        match (&self.0.lower, &self.0.upper) {
+            (Bound::RightUnbounded, _) | (_, Bound::LeftUnbounded) => {
+                return f.write_str("empty");
+            }
+            (Bound::LeftUnbounded, _) => f.write_char('(')?,
+            (Bound::LeftOf(l), _) => {
+                f.write_char('[')?;
+                write_bound(f, l)?;
+            }
+            (Bound::RightOf(l), _) => {
+                f.write_char('(')?;
+                write_bound(f, l)?;
+            }
+        }
+        f.write_char(',')?;
+        match &self.0.upper {
+            Bound::LeftOf(u) => {
+                write_bound(f, u)?;
+                f.write_char(')')
+            }
+            Bound::RightOf(u) => {
+                write_bound(f, u)?;
+                f.write_char(']')
+            }
+            Bound::LeftUnbounded | Bound::RightUnbounded => f.write_char(')'),
+        }
+    }
+}
+
+/// Write a bound, adding quotes if needed.
+fn write_bound<T>(
+    f: &mut ::core::fmt::Formatter<'_>,
+    value: &T,
+) -> ::core::fmt::Result
+where
+    T: ::core::fmt::Display,
+{
+    let mut check = NeedsQuotes {
+        len: 0,
+        prefix: [0; KEYWORD_MAX_LEN],
+        special: false,
+    };
+    write!(check, "{}", value)?;
+    if check.len == 0 || check.special || check.is_keyword() {
+        f.write_char('"')?;
+        write!(Escape(f), "{}", value)?;
+        f.write_char('"')
+    } else {
+        write!(f, "{}", value)
+    }
+}
+
+/// Words that PostgreSQL gives a special meaning in some contexts.  Bounds
+/// with the same text are quoted, so that they are always read as values.
+const KEYWORDS: [&str; 4] = ["empty", "infinity", "-infinity", "+infinity"];
+const KEYWORD_MAX_LEN: usize = 9;
+
+/// Checks whether the display of a bound needs quotes
+struct NeedsQuotes {
+    len: usize,
+    prefix: [u8; KEYWORD_MAX_LEN],
+    special: bool,
+}
+
+impl NeedsQuotes {
+    /// Whether the text is one of the keywords (ignoring case)
+    fn is_keyword(&self) -> bool {
+        self.prefix.get(..self.len).map_or(false, |text| {
+            KEYWORDS
+                .iter()
+                .any(|k| text.eq_ignore_ascii_case(k.as_bytes()))
+        })
+    }
+}
+
+impl Write for NeedsQuotes {
+    fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+        for (idx, b) in s.bytes().enumerate() {
+            if let Some(p) = self.prefix.get_mut(self.len + idx) {
+                *p = b;
+            }
+        }
+        self.len += s.len();
+        self.special |= s.chars().any(|c| {
+            matches!(c, '"' | '\\' | '(' | ')' | '[' | ']' | ',')
+                || c.is_whitespace()
+        });
+        Ok(())
+    }
+}
+
+/// Doubles the quotes and backslashes in a quoted bound
+struct Escape<'a, 'b>(&'a mut ::core::fmt::Formatter<'b>);
+
+impl Write for Escape<'_, '_> {
+    fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+        for c in s.chars() {
+            if c == '"' || c == '\\' {
+                self.0.write_char(c)?;
+            }
+            self.0.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse a range.  The offset is the position of input in the whole string,
+/// and is used to report errors.
+pub(crate) fn parse_range<T, E>(
+    input: &str,
+    offset: usize,
+) -> Result<Interval<T>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let bytes = input.as_bytes();
+    let err = |pos: usize| ParseErrorAt::invalid(offset + pos);
+
+    let mut pos = skip_whitespace(bytes, 0);
+    if is_keyword(bytes, pos, "empty") {
+        pos = skip_whitespace(bytes, pos + 5);
+        return if pos == bytes.len() {
+            Ok(Interval::empty())
+        } else {
+            Err(err(pos))
+        };
+    }
+
+    let lo_incl = match bytes.get(pos) {
+        Some(b'[') => true,
+        Some(b'(') => false,
+        _ => return Err(err(pos)),
+    };
+    let lo_start = pos + 1;
+    let lo_end = scan(bytes, lo_start, b",])").map_err(err)?;
+    let lo = parse_bound(input, lo_start, lo_end, offset)?;
+    if bytes.get(lo_end) != Some(&b',') {
+        return Err(err(lo_end));
+    }
+
+    let up_start = lo_end + 1;
+    let up_end = scan(bytes, up_start, b",])").map_err(err)?;
+    let up = parse_bound(input, up_start, up_end, offset)?;
+    let up_incl = match bytes.get(up_end) {
+        Some(b']') => true,
+        Some(b')') => false,
+        _ => return Err(err(up_end)),
+    };
+
+    pos = skip_whitespace(bytes, up_end + 1);
+    if pos != bytes.len() {
+        return Err(err(pos));
+    }
+
+    Ok(build_interval(lo, lo_incl, up, up_incl))
+}
+
+/// Build an interval from the parsed bounds, where None is used for an
+/// unbounded side (in which case the inclusive flag is ignored).
+pub(crate) fn build_interval<T>(
+    lo: Option<T>,
+    lo_incl: bool,
+    up: Option<T>,
+    up_incl: bool,
+) -> Interval<T>
+where
+    T: PartialOrd + Bounded + NothingBetween,
+{
+    match (lo, up) {
+        (Some(lo), Some(up)) => match (lo_incl, up_incl) {
+            (true, true) => Interval::new_closed_closed(lo, up),
+            (true, false) => Interval::new_closed_open(lo, up),
+            (false, true) => Interval::new_open_closed(lo, up),
+            (false, false) => Interval::new_open_open(lo, up),
+        },
+        (Some(lo), None) if lo_incl => Interval::new_closed_unbounded(lo),
+        (Some(lo), None) => Interval::new_open_unbounded(lo),
+        (None, Some(up)) if up_incl => Interval::new_unbounded_closed(up),
+        (None, Some(up)) => Interval::new_unbounded_open(up),
+        (None, None) => Interval::doubly_unbounded(),
+    }
+}
+
+/// Parse a multirange, and return the list of its non-empty ranges, in the
+/// order they appear in input.
+#[cfg(feature = "std")]
+pub(crate) fn parse_multirange<T, E>(
+    input: &str,
+) -> Result<Vec<Interval<T>>, ParseErrorAt<E>>
+where
+    T: PartialOrd + Bounded + NothingBetween + ::core::str::FromStr<Err = E>,
+{
+    let bytes = input.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+    if bytes.get(pos) != Some(&b'{') {
+        return Err(ParseErrorAt::invalid(pos));
+    }
+    pos += 1;
+
+    let mut result = Vec::new();
+    let mut index = 0;
+    let mut first = true;
+    let mut expect_range = true;
+    loop {
+        pos = skip_whitespace(bytes, pos);
+        match bytes.get(pos) {
+            Some(b'[' | b'(') if expect_range => {
+                let end = scan(bytes, pos + 1, b"])").map_err(|end| {
+                    ParseErrorAt::invalid(end).for_member(index)
+                })?;
+                let range =
+                    input.get(pos..=end).ok_or(ParseErrorAt::invalid(pos))?;
+                let intv =
+                    parse_range(range, pos).map_err(|e| e.for_member(index))?;
+                index += 1;
+                if !intv.is_empty() {
+                    result.push(intv);
+                }
+                pos = end + 1;
+                expect_range = false;
+            }
+            Some(_) if expect_range && is_keyword(bytes, pos, "empty") => {
+                pos += 5;
+                index += 1;
+                expect_range = false;
+            }
+            Some(b'}') if expect_range && first => {
+                pos += 1;
+                break;
+            }
+            Some(b',') if !expect_range => {
+                pos += 1;
+                expect_range = true;
+            }
+            Some(b'}') if !expect_range => {
+                pos += 1;
+                break;
+            }
+            _ => return Err(ParseErrorAt::invalid(pos)),
+        }
+        first = false;
+    }
+
+    pos = skip_whitespace(bytes, pos);
+    if pos != bytes.len() {
+        return Err(ParseErrorAt::invalid(pos));
+    }
+    Ok(result)
+}
+
+/// Return the position of the first non-whitespace character at or after pos
+fn skip_whitespace(bytes: &[u8], mut pos: usize) -> usize {
+    while bytes.get(pos).map_or(false, u8::is_ascii_whitespace) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Whether the keyword appears at the given position (ignoring case)
+fn is_keyword(bytes: &[u8], pos: usize, keyword: &str) -> bool {
+    bytes
+        .get(pos..pos + keyword.len())
+        .map_or(false, |w| w.eq_ignore_ascii_case(keyword.as_bytes()))
+}
+
+/// Return the position of the first character from stops that is neither
+/// quoted nor escaped, starting at pos.  On error, returns the position of
+/// the end of the input.
+fn scan(bytes: &[u8], mut pos: usize, stops: &[u8]) -> Result<usize, usize> {
+    let mut in_quote = false;
+    loop {
+        match bytes.get(pos) {
+            None => return Err(pos),
+            Some(b'\\') => pos += 1,
+            Some(b'"') => {
+                if in_quote && bytes.get(pos + 1) == Some(&b'"') {
+                    pos += 1;
+                } else {
+                    in_quote = !in_quote;
+                }
+            }
+            Some(c) if !in_quote && stops.contains(c) => return Ok(pos),
+            Some(_) => {}
+        }
+        pos += 1;
+    }
+}
+
+/// Parse the bound in input[start..end].  Returns None if the range is
+/// unbounded on that side.
+fn parse_bound<T, E>(
+    input: &str,
+    start: usize,
+    end: usize,
+    offset: usize,
+) -> Result<Option<T>, ParseErrorAt<E>>
+where
+    T: ::core::str::FromStr<Err = E>,
+{
+    let raw = input
+        .get(start..end)
+        .ok_or(ParseErrorAt::invalid(offset + start))?;
+    let start = offset + start;
+    let text = unescape(raw).ok_or(ParseErrorAt::invalid(start))?;
+    let text = match &text {
+        Text::Unbounded => return Ok(None),
+        Text::Borrowed(t) => t,
+        #[cfg(feature = "std")]
+        Text::Owned(t) => t.as_str(),
+    };
+    text.parse()
+        .map(Some)
+        .map_err(|e| ParseErrorAt::bound(start, e))
+}
+
+/// The text of a bound, after removing quotes and escape characters
+enum Text<'a> {
+    Unbounded,
+    Borrowed(&'a str),
+    #[cfg(feature = "std")]
+    Owned(String),
+}
+
+/// Remove quotes and escape characters from the text of a bound.  Returns
+/// None if this requires memory allocation and the `std` feature is disabled.
+fn unescape(raw: &str) -> Option<Text<'_>> {
+    if raw.is_empty() {
+        return Some(Text::Unbounded);
+    }
+    if !raw.contains(&['"', '\\'][..]) {
+        return Some(Text::Borrowed(raw));
+    }
+    if let Some(inner) = raw.strip_prefix('"').and_then(|t| t.strip_suffix('"'))
+    {
+        if !inner.contains(&['"', '\\'][..]) {
+            return Some(Text::Borrowed(inner));
+        }
+    }
+    unescape_owned(raw)
+}
+
+#[cfg(feature = "std")]
+fn unescape_owned(raw: &str) -> Option<Text<'_>> {
+    let mut result = String::new();
+    let mut in_quote = false;
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.extend(chars.next()),
+            '"' if in_quote && chars.peek() == Some(&'"') => {
+                chars.next();
+                result.push('"');
+            }
+            '"' => in_quote = !in_quote,
+            c => result.push(c),
+        }
+    }
+    Some(Text::Owned(result))
+}
+
+#[cfg(not(feature = "std"))]
+fn unescape_owned(_raw: &str) -> Option<Text<'_>> {
+    None
+}
@@ -0,0 +1,346 @@
+//! Floats with the semantics of real numbers, for validated numerics.
+
+use crate::arithmetic::Arithmetic;
+#[cfg(feature = "std")]
+use crate::bounds::Bound;
+#[cfg(feature = "std")]
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use crate::step::Bounded;
+
+/// A float interpreted as a real number.
+///
+/// Operations on intervals of `Rigorous` values are guaranteed to enclose
+/// the exact real result: the lower bound of each result is rounded down
+/// and its upper bound rounded up, one ULP at a time.
+///
+/// Also, unlike for `f64`, an interval like `(1.0, 1.0 + f64::EPSILON)` is
+/// not empty, since it contains real numbers, even if none of them can be
+/// represented.
+/// ```
+/// #  use rust_intervals::{interval, Interval, Rigorous};
+///    let tenth = interval!(Rigorous(0.1), Rigorous(0.1), "[]");
+///    let sum = (&tenth + &tenth).and_then(|s| s + &tenth).unwrap();
+///    assert!(sum.contains(Rigorous(0.1 + 0.1 + 0.1)));
+///    assert!(sum.contains(Rigorous(0.3)));
+///    assert!(!Interval::new_open_open(
+///        Rigorous(1.0),
+///        Rigorous(1.0 + f64::EPSILON),
+///    ).is_empty());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Rigorous(pub f64);
+
+impl From<f64> for Rigorous {
+    fn from(value: f64) -> Self {
+        Rigorous(value)
+    }
+}
+
+impl ::core::fmt::Display for Rigorous {
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        ::core::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl ::core::str::FromStr for Rigorous {
+    type Err = ::core::num::ParseFloatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse().map(Rigorous)
+    }
+}
+
+impl NothingBetween for Rigorous {
+    fn nothing_between(&self, _other: &Self) -> bool {
+        false // there is always a real between two others
+    }
+}
+
+impl Bounded for Rigorous {
+    fn min_value() -> Self {
+        Rigorous(f64::NEG_INFINITY)
+    }
+    fn max_value() -> Self {
+        Rigorous(f64::INFINITY)
+    }
+}
+
+/// The smallest float greater than value, like `f64::next_up()` which
+/// requires a more recent compiler.
+pub(crate) fn next_up(value: f64) -> f64 {
+    let bits = value.to_bits();
+    if value.is_nan() || value == f64::INFINITY {
+        value
+    } else if value == 0.0 {
+        f64::from_bits(1) // smallest positive subnormal, also for -0.0
+    } else if value > 0.0 {
+        f64::from_bits(bits + 1)
+    } else {
+        f64::from_bits(bits - 1)
+    }
+}
+
+/// The largest float less than value, like `f64::next_down()`.
+pub(crate) fn next_down(value: f64) -> f64 {
+    -next_up(-value)
+}
+
+/// Only NaN results are rejected.  Overflows give infinite values, which
+/// are still valid bounds after rounding.
+impl Arithmetic for Rigorous {
+    fn zero() -> Self {
+        Rigorous(0.0)
+    }
+    fn checked_add(&self, rhs: &Self) -> Option<Self> {
+        Some(self.0 + rhs.0).filter(|r| !r.is_nan()).map(Rigorous)
+    }
+    fn checked_sub(&self, rhs: &Self) -> Option<Self> {
+        Some(self.0 - rhs.0).filter(|r| !r.is_nan()).map(Rigorous)
+    }
+    fn checked_mul(&self, rhs: &Self) -> Option<Self> {
+        Some(self.0 * rhs.0).filter(|r| !r.is_nan()).map(Rigorous)
+    }
+    fn checked_div(&self, rhs: &Self) -> Option<Self> {
+        if rhs.0 == 0.0 {
+            None
+        } else {
+            Some(self.0 / rhs.0).filter(|r| !r.is_nan()).map(Rigorous)
+        }
+    }
+    fn checked_neg(&self) -> Option<Self> {
+        Some(Rigorous(-self.0))
+    }
+    fn round_down(self) -> Self {
+        Rigorous(next_down(self.0))
+    }
+    fn round_up(self) -> Self {
+        Rigorous(next_up(self.0))
+    }
+}
+
+/// The functions from the standard library are not always correctly
+/// rounded, so we widen their results by a few ULPs, which covers the
+/// accuracy of common implementations.
+#[cfg(feature = "std")]
+const FUNCTION_ULPS: u32 = 4;
+
+#[cfg(feature = "std")]
+fn widen_down(mut value: f64, ulps: u32) -> f64 {
+    for _ in 0..ulps {
+        value = next_down(value);
+    }
+    value
+}
+
+#[cfg(feature = "std")]
+fn widen_up(mut value: f64, ulps: u32) -> f64 {
+    for _ in 0..ulps {
+        value = next_up(value);
+    }
+    value
+}
+
+/// Encloses `|value|^n`, using multiplications rounded outward.
+#[cfg(feature = "std")]
+fn abs_pow(value: f64, mut n: u32) -> (f64, f64) {
+    let mut base = (value.abs(), value.abs());
+    let mut result = (1.0_f64, 1.0_f64);
+    while n > 0 {
+        if n % 2 == 1 {
+            result = (
+                next_down(result.0 * base.0).max(0.0),
+                next_up(result.1 * base.1),
+            );
+        }
+        base = (
+            next_down(base.0 * base.0).max(0.0),
+            next_up(base.1 * base.1),
+        );
+        n /= 2;
+    }
+    result
+}
+
+#[cfg(feature = "std")]
+impl Interval<Rigorous> {
+    /// The two ends of a non-empty interval, using infinities for unbounded
+    /// intervals, and whether they are included.
+    fn float_ends(&self) -> Option<((f64, bool), (f64, bool))> {
+        if self.is_empty() {
+            return None;
+        }
+        let lo = match &self.lower {
+            Bound::LeftOf(v) => (v.0, true),
+            Bound::RightOf(v) => (v.0, false),
+            Bound::LeftUnbounded | Bound::RightUnbounded => {
+                (f64::NEG_INFINITY, false)
+            }
+        };
+        let hi = match &self.upper {
+            Bound::LeftOf(v) => (v.0, false),
+            Bound::RightOf(v) => (v.0, true),
+            Bound::LeftUnbounded | Bound::RightUnbounded => {
+                (f64::INFINITY, false)
+            }
+        };
+        Some((lo, hi))
+    }
+
+    fn from_float_ends(lo: (f64, bool), hi: (f64, bool)) -> Self {
+        let lower = match lo {
+            (v, _) if v == f64::NEG_INFINITY => Bound::LeftUnbounded,
+            (v, true) => Bound::LeftOf(Rigorous(v)),
+            (v, false) => Bound::RightOf(Rigorous(v)),
+        };
+        let upper = match hi {
+            (v, _) if v == f64::INFINITY => Bound::RightUnbounded,
+            (v, true) => Bound::RightOf(Rigorous(v)),
+            (v, false) => Bound::LeftOf(Rigorous(v)),
+        };
+        Interval::from_bounds(&lower, &upper)
+    }
+
+    /// Applies a non-decreasing function to both ends, and widens the
+    /// result, which is then restricted to the range of the function.
+    fn map_increasing(
+        &self,
+        f: fn(f64) -> f64,
+        ulps: u32,
+        range: (f64, f64),
+    ) -> Self {
+        match self.float_ends() {
+            None => Interval::empty(),
+            Some(((lo, lo_incl), (hi, hi_incl))) => Interval::from_float_ends(
+                (widen_down(f(lo), ulps).max(range.0), lo_incl),
+                (widen_up(f(hi), ulps).min(range.1), hi_incl),
+            ),
+        }
+    }
+
+    /// Encloses a function with period 2π, which reaches its maximum 1 at
+    /// `peak + 2kπ` and its minimum -1 at `peak + π + 2kπ`.
+    fn map_periodic(&self, f: fn(f64) -> f64, peak: f64) -> Self {
+        use ::core::f64::consts::{PI, TAU};
+        let ((a, _), (b, _)) = match self.float_ends() {
+            None => return Interval::empty(),
+            Some(ends) => ends,
+        };
+        let full = (-1.0, 1.0);
+        let (lo, hi) = if a.is_infinite() || b.is_infinite() || b - a >= TAU {
+            full
+        } else {
+            // Whether [a, b] might contain `offset + 2kπ`, with a margin
+            // larger than the rounding errors.
+            let margin = (1.0 + a.abs().max(b.abs())) * 1e-14;
+            let contains = |offset: f64| {
+                ((b - offset) / TAU + margin).floor()
+                    >= ((a - offset) / TAU - margin).ceil()
+            };
+            let (fa, fb) = (f(a), f(b));
+            (
+                if contains(peak + PI) {
+                    full.0
+                } else {
+                    widen_down(fa.min(fb), FUNCTION_ULPS).max(full.0)
+                },
+                if contains(peak) {
+                    full.1
+                } else {
+                    widen_up(fa.max(fb), FUNCTION_ULPS).min(full.1)
+                },
+            )
+        };
+        Interval::from_float_ends((lo, true), (hi, true))
+    }
+
+    /// Encloses the square root of all non-negative values in self.
+    /// ```
+    /// #  use rust_intervals::{Interval, Rigorous};
+    ///    let intv = Interval::new_closed_closed(Rigorous(-1.0), Rigorous(2.0));
+    ///    let root = intv.sqrt();
+    ///    assert_eq!(root.lower(), Some(&Rigorous(0.0)));
+    ///    assert!(root.contains(Rigorous(2.0_f64.sqrt())));
+    /// ```
+    #[must_use]
+    pub fn sqrt(&self) -> Self {
+        self.intersection(Interval::new_closed_unbounded(Rigorous(0.0)))
+            .map_increasing(f64::sqrt, 1, (0.0, f64::INFINITY))
+    }
+
+    /// Encloses the exponential of all values in self.
+    #[must_use]
+    pub fn exp(&self) -> Self {
+        self.map_increasing(f64::exp, FUNCTION_ULPS, (0.0, f64::INFINITY))
+    }
+
+    /// Encloses the natural logarithm of all positive values in self.
+    #[must_use]
+    pub fn ln(&self) -> Self {
+        self.intersection(Interval::new_open_unbounded(Rigorous(0.0)))
+            .map_increasing(
+                f64::ln,
+                FUNCTION_ULPS,
+                (f64::NEG_INFINITY, f64::INFINITY),
+            )
+    }
+
+    /// Encloses the sine of all values in self.
+    /// ```
+    /// #  use rust_intervals::{Interval, Rigorous};
+    ///    let intv = Interval::new_closed_closed(Rigorous(0.0), Rigorous(2.0));
+    ///    let sine = intv.sin();
+    ///    assert_eq!(sine.upper(), Some(&Rigorous(1.0)));
+    ///    assert!(sine.contains(Rigorous(0.0)));
+    /// ```
+    #[must_use]
+    pub fn sin(&self) -> Self {
+        self.map_periodic(f64::sin, ::core::f64::consts::FRAC_PI_2)
+    }
+
+    /// Encloses the cosine of all values in self.
+    #[must_use]
+    pub fn cos(&self) -> Self {
+        self.map_periodic(f64::cos, 0.0)
+    }
+
+    /// Encloses `x^n` for all values x in self.
+    /// Negative powers can be computed by dividing `1` by the result.
+    /// ```
+    /// #  use rust_intervals::{Interval, Rigorous};
+    ///    let intv = Interval::new_closed_closed(Rigorous(-2.0), Rigorous(1.0));
+    ///    let square = intv.powi(2);
+    ///    assert_eq!(square.lower(), Some(&Rigorous(0.0)));
+    ///    assert!(square.contains(Rigorous(4.0)));
+    /// ```
+    #[must_use]
+    pub fn powi(&self, n: u32) -> Self {
+        let ((a, a_incl), (b, b_incl)) = match self.float_ends() {
+            None => return Interval::empty(),
+            Some(_) if n == 0 => {
+                return Interval::new_single(Rigorous(1.0));
+            }
+            Some(ends) => ends,
+        };
+        let (a_lo, a_hi) = abs_pow(a, n);
+        let (b_lo, b_hi) = abs_pow(b, n);
+        if n % 2 == 1 {
+            // Odd powers are increasing and preserve the sign
+            let lo = if a < 0.0 { -a_hi } else { a_lo };
+            let hi = if b < 0.0 { -b_lo } else { b_hi };
+            Interval::from_float_ends((lo, a_incl), (hi, b_incl))
+        } else if a >= 0.0 {
+            Interval::from_float_ends((a_lo, a_incl), (b_hi, b_incl))
+        } else if b <= 0.0 {
+            Interval::from_float_ends((b_lo, b_incl), (a_hi, a_incl))
+        } else {
+            // Zero is in the interval
+            let hi = match a_hi.partial_cmp(&b_hi) {
+                Some(::core::cmp::Ordering::Greater) => (a_hi, a_incl),
+                Some(::core::cmp::Ordering::Equal) => (a_hi, a_incl || b_incl),
+                _ => (b_hi, b_incl),
+            };
+            Interval::from_float_ends((0.0, true), hi)
+        }
+    }
+}
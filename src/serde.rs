@@ -109,6 +109,69 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<T, P> Serialize for crate::multi::IntervalSet<T, P>
+where
+    T: PartialOrd + NothingBetween + Serialize,
+    P: crate::multi::Policy<T>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, T, P> Deserialize<'de> for crate::multi::IntervalSet<T, P>
+where
+    T: PartialOrd + NothingBetween + DeserializeOwned + Bounded + Clone,
+    P: crate::multi::Policy<T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // The intervals might not be sorted or normalized, so we go through
+        // the policy again.
+        let intvs =
+            Vec::<crate::intervals::Interval<T>>::deserialize(deserializer)?;
+        Ok(crate::multi::IntervalSet::from_unsorted(intvs))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, P> Serialize for crate::multi_btree::BTreeIntervalSet<T, P>
+where
+    T: PartialOrd + NothingBetween + Serialize,
+    P: crate::multi::Policy<T>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'de, T, P> Deserialize<'de> for crate::multi_btree::BTreeIntervalSet<T, P>
+where
+    T: Ord + NothingBetween + DeserializeOwned + Bounded + Clone,
+    P: crate::multi::Policy<T>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        // Same format as IntervalSet
+        let intvs =
+            Vec::<crate::intervals::Interval<T>>::deserialize(deserializer)?;
+        Ok(crate::multi_btree::BTreeIntervalSet::from_unsorted(intvs))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::nothing_between::NothingBetween;
@@ -188,4 +251,95 @@ mod test {
             "DoublyUnbounded",
         );
     }
+
+    fn roundtrip_set<T, P>(
+        set: IntervalSet<T, P>,
+        json_str: &str,
+        ron_str: &str,
+    ) where
+        T: PartialOrd
+            + NothingBetween
+            + Serialize
+            + DeserializeOwned
+            + Debug
+            + Bounded
+            + Clone,
+        P: Policy<T> + Debug,
+    {
+        assert_eq!(serde_json::to_string(&set).unwrap(), json_str);
+        assert_eq!(ron::to_string(&set).unwrap(), ron_str);
+        assert_eq!(
+            serde_json::from_str::<IntervalSet<T, P>>(json_str).unwrap(),
+            set,
+        );
+        assert_eq!(ron::from_str::<IntervalSet<T, P>>(ron_str).unwrap(), set);
+    }
+
+    #[test]
+    fn test_serde_set() {
+        roundtrip_set(IntervalSet::<u32>::empty(), "[]", "[]");
+        roundtrip_set(
+            IntervalSet::new_joining([interval!(1, 3), interval!(5, "[inf")]),
+            "[{\"ClosedOpen\":[1,3]},{\"ClosedUnbounded\":5}]",
+            "[ClosedOpen(1,3),ClosedUnbounded(5)]",
+        );
+        roundtrip_set(
+            IntervalSet::new_separating([interval!(1, 3), interval!(3, 5)]),
+            "[{\"ClosedOpen\":[1,3]},{\"ClosedOpen\":[3,5]}]",
+            "[ClosedOpen(1,3),ClosedOpen(3,5)]",
+        );
+
+        // Unsorted and overlapping intervals are normalized
+        let set: IntervalSet<f32> = serde_json::from_str(
+            "[{\"ClosedOpen\":[5.0,8.0]},\"Empty\",{\"ClosedOpen\":[1.0,3.0]},
+              {\"ClosedClosed\":[2.0,6.0]}]",
+        )
+        .unwrap();
+        assert_eq!(set, IntervalSet::from_pair(Pair::One(interval!(1.0, 8.0))));
+        let set: IntervalSet<u32, Splitting> =
+            ron::from_str("[ClosedOpen(5,8),ClosedOpen(1,6)]").unwrap();
+        assert_eq!(
+            set,
+            IntervalSet::new_splitting([
+                interval!(1, 5),
+                interval!(5, 6),
+                interval!(6, 8),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_serde_btree_set() {
+        let set = BTreeIntervalSet::new_joining([
+            interval!(1, 3),
+            interval!(5, "[inf"),
+        ]);
+        let json_str = "[{\"ClosedOpen\":[1,3]},{\"ClosedUnbounded\":5}]";
+        assert_eq!(serde_json::to_string(&set).ok().as_deref(), Some(json_str));
+        assert_eq!(
+            ron::to_string(&set).ok().as_deref(),
+            Some("[ClosedOpen(1,3),ClosedUnbounded(5)]"),
+        );
+        assert_eq!(
+            serde_json::from_str::<BTreeIntervalSet<u32>>(json_str).ok(),
+            Some(set.clone()),
+        );
+
+        // Same format as IntervalSet
+        let vec: IntervalSet<u32> = set.clone().into();
+        assert_eq!(serde_json::to_string(&vec).ok().as_deref(), Some(json_str));
+
+        // Unsorted and overlapping intervals are normalized
+        assert_eq!(
+            ron::from_str::<BTreeIntervalSet<u32, Splitting>>(
+                "[ClosedOpen(5,8),Empty,ClosedOpen(1,6)]"
+            )
+            .ok(),
+            Some(BTreeIntervalSet::new_splitting([
+                interval!(1, 5),
+                interval!(5, 6),
+                interval!(6, 8),
+            ])),
+        );
+    }
 }
@@ -0,0 +1,226 @@
+use crate::bounds::Bound;
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+
+#[cfg(feature = "std")]
+use crate::measure::Measure;
+#[cfg(feature = "std")]
+use crate::multi::IntervalSet;
+#[cfg(feature = "std")]
+use crate::multi_separating::Separating;
+#[cfg(feature = "std")]
+use crate::step::Step;
+#[cfg(feature = "std")]
+use crate::translate::Translate;
+#[cfg(feature = "std")]
+use ::core::cmp::Ordering;
+#[cfg(feature = "std")]
+use ::core::convert::TryFrom;
+
+/// Which part of a split interval contains the value used to split it.
+/// See [`Interval::split_at()`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitSide {
+    /// The value goes to the left part
+    Left,
+
+    /// The value goes to the right part
+    Right,
+}
+
+impl<T> Interval<T> {
+    /// Splits the interval into the values left of value, and the values
+    /// right of it.  The value itself (if it is in self) goes to the part
+    /// given by side.  Either part might be empty.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval, SplitSide};
+    ///    let intv1 = interval!(1, 10);
+    ///    assert_eq!(
+    ///        intv1.split_at(&4, SplitSide::Left),
+    ///        (interval!(1, 4, "[]"), interval!(4, 10, "()")),
+    ///    );
+    ///    assert_eq!(
+    ///        intv1.split_at(&4, SplitSide::Right),
+    ///        (interval!(1, 4, "[)"), interval!(4, 10, "[)")),
+    ///    );
+    ///    assert_eq!(
+    ///        intv1.split_at(&20, SplitSide::Right),
+    ///        (intv1, Interval::empty()),
+    ///    );
+    /// ```
+    pub fn split_at(&self, value: &T, side: SplitSide) -> (Self, Self)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        let border = match side {
+            SplitSide::Left => Bound::RightOf(value.clone()),
+            SplitSide::Right => Bound::LeftOf(value.clone()),
+        };
+        let left = self.intersection(Interval {
+            lower: Bound::LeftUnbounded,
+            upper: border.clone(),
+        });
+        let right = self.intersection(Interval {
+            lower: border,
+            upper: Bound::RightUnbounded,
+        });
+        (left, right)
+    }
+
+    /// Splits the interval at each of the points, which do not need to be
+    /// sorted.  Each point goes to the interval on its right, so that all
+    /// intervals but the last one are closed-open, as in
+    /// `[a, p1) [p1, p2) [p2, b]`.  Points outside of self are ignored, as
+    /// well as points that cannot be compared, like NaN.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalSet};
+    ///    let intv1 = interval!(0, 10, "[]");
+    ///    let split = intv1.split_at_many([5, 2]);
+    ///    assert_eq!(
+    ///        split.iter().collect::<Vec<_>>(),
+    ///        vec![&interval!(0, 2), &interval!(2, 5), &interval!(5, 10, "[]")],
+    ///    );
+    ///    assert_eq!(
+    ///        interval!(0.0, 1.0).split_at_many([f64::NAN]).len(),
+    ///        1,
+    ///    );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn split_at_many<I>(&self, points: I) -> IntervalSet<T, Separating>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        I: IntoIterator<Item = T>,
+    {
+        let mut points = points
+            .into_iter()
+            .filter(|p| p.partial_cmp(p).is_some())
+            .collect::<Vec<_>>();
+        // The remaining points are totally ordered
+        points.sort_by(|p1, p2| p1.partial_cmp(p2).unwrap_or(Ordering::Equal));
+        let mut result = Vec::with_capacity(points.len() + 1);
+        let mut rest = self.clone();
+        for p in &points {
+            let (left, right) = rest.split_at(p, SplitSide::Right);
+            result.push(left);
+            rest = right;
+        }
+        result.push(rest);
+        IntervalSet::from_unsorted(result)
+    }
+
+    /// Splits the interval into n intervals that contain the same number of
+    /// values (the first intervals might contain one more value when the
+    /// values cannot be evenly distributed).  The first and last intervals
+    /// preserve the bounds of self, all others are closed-open.
+    /// There are fewer than n intervals when self has fewer than n values.
+    /// Returns None if n is zero, if self is unbounded, or if its number of
+    /// values cannot be computed.
+    /// See [`Interval::partition_by_length()`] for continuous types.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let split = interval!(0, 10).partition(3).unwrap();
+    ///    assert_eq!(
+    ///        split.iter().collect::<Vec<_>>(),
+    ///        vec![&interval!(0, 4), &interval!(4, 7), &interval!(7, 10)],
+    ///    );
+    ///    assert!(interval!(0, "inf").partition(3).is_none());
+    ///    assert_eq!(
+    ///        interval!(0_u64, u64::MAX, "[]").partition(4).map(|s| s.len()),
+    ///        Some(4),
+    ///    );
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn partition(&self, n: usize) -> Option<IntervalSet<T, Separating>>
+    where
+        T: PartialOrd + NothingBetween + Clone + Step,
+    {
+        if n == 0 {
+            return None;
+        }
+        if self.is_empty() {
+            return Some(IntervalSet::empty());
+        }
+        let first = match &self.lower {
+            Bound::LeftOf(lo) => lo.clone(),
+            Bound::RightOf(lo) => lo.forward(1)?,
+            Bound::LeftUnbounded | Bound::RightUnbounded => return None,
+        };
+        let last = match &self.upper {
+            Bound::LeftOf(up) => up.backward(1)?,
+            Bound::RightOf(up) => up.clone(),
+            Bound::LeftUnbounded | Bound::RightUnbounded => return None,
+        };
+        // The number of values might not fit in usize, e.g. for u64
+        let count = first.elements_between_u128(&last)?.checked_add(1)?;
+        let parts = u128::try_from(n).ok()?.min(count);
+        let (size, extra) = (count / parts, count % parts);
+
+        let mut result = Vec::with_capacity(usize::try_from(parts).ok()?);
+        let mut lower = self.lower.clone();
+        let mut start = first;
+        for idx in 0..parts - 1 {
+            let next = start.forward_u128(size + u128::from(idx < extra))?;
+            result.push(Interval {
+                lower,
+                upper: Bound::LeftOf(next.clone()),
+            });
+            lower = Bound::LeftOf(next.clone());
+            start = next;
+        }
+        result.push(Interval {
+            lower,
+            upper: self.upper.clone(),
+        });
+        Some(IntervalSet::from_unsorted(result))
+    }
+
+    /// Splits the interval into n intervals of the same length, for
+    /// continuous types like floats, decimals or dates, where
+    /// [`Interval::partition()`] cannot count values.  The first and last
+    /// intervals preserve the bounds of self, all others are closed-open.
+    /// Returns None if n is zero, if self is unbounded, or if the length
+    /// cannot be divided (see [`Measure::fraction()`]).
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let split = interval!(0.0, 1.0, "[]").partition_by_length(4).unwrap();
+    ///    assert_eq!(
+    ///        split.iter().collect::<Vec<_>>(),
+    ///        vec![
+    ///            &interval!(0.0, 0.25),
+    ///            &interval!(0.25, 0.5),
+    ///            &interval!(0.5, 0.75),
+    ///            &interval!(0.75, 1.0, "[]"),
+    ///        ],
+    ///    );
+    ///    assert!(interval!(0.0, "inf").partition_by_length(3).is_none());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn partition_by_length(
+        &self,
+        n: usize,
+    ) -> Option<IntervalSet<T, Separating>>
+    where
+        T: PartialOrd + NothingBetween + Clone + Measure + Translate<T::Output>,
+    {
+        if n == 0 {
+            return None;
+        }
+        if self.is_empty() {
+            return Some(IntervalSet::empty());
+        }
+        let parts = u32::try_from(n).ok()?;
+        let (first, last) = (self.lower()?, self.upper()?);
+        let length = first.measure(last, (true, false))?;
+
+        let mut result = Vec::with_capacity(n);
+        let mut lower = self.lower.clone();
+        for idx in 1..parts {
+            let delta = T::fraction(&length, idx, parts)?;
+            let upper = Bound::LeftOf(first.checked_add_delta(&delta)?);
+            result.push(Interval::from_bounds(&lower, &upper));
+            lower = upper;
+        }
+        result.push(Interval::from_bounds(&lower, &self.upper));
+        Some(IntervalSet::from_unsorted(result))
+    }
+}
@@ -26,6 +26,35 @@ where
     /// assert_eq!(1_u8.elements_between(&3), Some(2));
     /// ```
     fn elements_between(&self, other: &Self) -> Option<usize>;
+
+    /// Same as [`Step::elements_between()`], for numbers of elements that
+    /// might not fit in usize.  This is used by [`Interval::cardinality()`].
+    /// ```
+    /// use rust_intervals::Step;
+    /// assert_eq!(0_u64.elements_between_u128(&u64::MAX), Some(u64::MAX.into()));
+    /// ```
+    ///
+    /// [`Interval::cardinality()`]: crate::Interval::cardinality
+    fn elements_between_u128(&self, other: &Self) -> Option<u128> {
+        self.elements_between(other).and_then(|c| c.try_into().ok())
+    }
+
+    /// Same as [`Step::forward()`], for steps that might not fit in usize.
+    /// The default implementation moves forward by chunks of `usize::MAX`.
+    /// ```
+    /// use rust_intervals::Step;
+    /// assert_eq!(0_u128.forward_u128(1 << 100), Some(1 << 100));
+    /// assert_eq!(1_u8.forward_u128(255), None);
+    /// ```
+    fn forward_u128(&self, mut step: u128) -> Option<Self> {
+        let mut value = self.forward(0)?;
+        while step > 0 {
+            let chunk = step.try_into().unwrap_or(usize::MAX);
+            value = value.forward(chunk)?;
+            step -= chunk as u128;
+        }
+        Some(value)
+    }
 }
 
 macro_rules! step_for_int {
@@ -43,6 +72,25 @@ macro_rules! step_for_int {
                     Some(d) => d.try_into().ok(),
                 }
             }
+            fn elements_between_u128(&self, other: &Self) -> Option<u128> {
+                if other < self {
+                    None
+                } else {
+                    // Casting signed values is fine, since the subtraction
+                    // is performed modulo 2^128
+                    Some((*other as u128).wrapping_sub(*self as u128))
+                }
+            }
+            fn forward_u128(&self, step: u128) -> Option<Self> {
+                // Truncating is fine: the distance is only preserved when
+                // the step fits in the type and there is no overflow.
+                let next = (*self as u128).wrapping_add(step) as Self;
+                if self.elements_between_u128(&next) == Some(step) {
+                    Some(next)
+                } else {
+                    None
+                }
+            }
         }
     };
 }
@@ -69,6 +117,7 @@ step_for_int!(i8);
 step_for_int!(i16);
 step_for_int!(i32);
 step_for_int!(i64);
+step_for_int!(i128);
 step_for_int!(isize);
 step_for_int!(usize);
 
@@ -81,11 +130,39 @@ bounded_for_type!(i8);
 bounded_for_type!(i16);
 bounded_for_type!(i32);
 bounded_for_type!(i64);
+bounded_for_type!(i128);
 bounded_for_type!(isize);
 bounded_for_type!(usize);
 bounded_for_type!(f32);
 bounded_for_type!(f64);
 
+/// Characters skip the surrogate range, which contains no valid char.
+impl Step for char {
+    fn forward(&self, step: usize) -> Option<Self> {
+        let start = *self as u32;
+        let mut next = start.checked_add(step.try_into().ok()?)?;
+        if start < 0xD800 && next >= 0xD800 {
+            next = next.checked_add(0x800)?;
+        }
+        char::from_u32(next)
+    }
+    fn backward(&self, step: usize) -> Option<Self> {
+        let start = *self as u32;
+        let mut prev = start.checked_sub(step.try_into().ok()?)?;
+        if start >= 0xE000 && prev < 0xE000 {
+            prev = prev.checked_sub(0x800)?;
+        }
+        char::from_u32(prev)
+    }
+    fn elements_between(&self, other: &Self) -> Option<usize> {
+        let mut diff = (*other as u32).checked_sub(*self as u32)?;
+        if (*self as u32) < 0xD800 && (*other as u32) >= 0xE000 {
+            diff -= 0x800;
+        }
+        diff.try_into().ok()
+    }
+}
+
 impl Bounded for char {
     fn min_value() -> Self {
         '\0'
@@ -0,0 +1,280 @@
+//! Lazy set operations on sorted iterators of intervals.
+//!
+//! The functions in this module combine iterators of intervals on the fly,
+//! without building an [`crate::IntervalSet`] in memory, so they can be used
+//! for streams of intervals that would not fit in memory.
+//!
+//! All input iterators must return intervals sorted by their lower bound
+//! (as done by [`crate::IntervalSet::iter()`] for instance).  They might
+//! overlap, touch, or be empty.
+//! The output is always normalized: the intervals are sorted, not empty, and
+//! neither overlap nor touch (similar to the [`crate::Joining`] policy).
+
+use crate::intervals::Interval;
+use crate::leftmostiter::LeftMostIter;
+use crate::nothing_between::NothingBetween;
+use crate::pairs::Pair;
+#[cfg(feature = "std")]
+use ::core::cmp::Ordering;
+#[cfg(feature = "std")]
+use ::std::collections::BinaryHeap;
+
+/// Joins the overlapping or contiguous intervals returned by a sorted
+/// iterator.
+/// ```
+/// #  use rust_intervals::{coalesce, interval};
+///    let intvs = [interval!(1, 3), interval!(2, 4), interval!(4, 5),
+///                 interval!(8, 10)];
+///    assert_eq!(
+///        coalesce(intvs).collect::<Vec<_>>(),
+///        [interval!(1, 5), interval!(8, 10)],
+///    );
+/// ```
+pub fn coalesce<T, I>(iter: I) -> Coalesce<T, I::IntoIter>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    I: IntoIterator<Item = Interval<T>>,
+{
+    Coalesce {
+        iter: iter.into_iter(),
+        current: None,
+    }
+}
+
+/// Returns the union of two sorted iterators of intervals.
+/// ```
+/// #  use rust_intervals::{interval, union_iter};
+///    let left = [interval!(1, 3), interval!(8, 10)];
+///    let right = [interval!(2, 5)];
+///    assert_eq!(
+///        union_iter(left, right).collect::<Vec<_>>(),
+///        [interval!(1, 5), interval!(8, 10)],
+///    );
+/// ```
+pub fn union_iter<T, L, R>(
+    left: L,
+    right: R,
+) -> impl Iterator<Item = Interval<T>>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    L: IntoIterator<Item = Interval<T>>,
+    R: IntoIterator<Item = Interval<T>>,
+{
+    coalesce(LeftMostIter::new(
+        left.into_iter().filter(|i| !i.is_empty()),
+        right.into_iter().filter(|i| !i.is_empty()),
+    ))
+}
+
+/// Returns the intersection of two sorted iterators of intervals.
+/// ```
+/// #  use rust_intervals::{interval, intersection_iter};
+///    let left = [interval!(1, 3), interval!(8, 10)];
+///    let right = [interval!(2, 9)];
+///    assert_eq!(
+///        intersection_iter(left, right).collect::<Vec<_>>(),
+///        [interval!(2, 3), interval!(8, 9)],
+///    );
+/// ```
+pub fn intersection_iter<T, L, R>(
+    left: L,
+    right: R,
+) -> impl Iterator<Item = Interval<T>>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    L: IntoIterator<Item = Interval<T>>,
+    R: IntoIterator<Item = Interval<T>>,
+{
+    let mut left = coalesce(left).peekable();
+    let mut right = coalesce(right).peekable();
+    ::core::iter::from_fn(move || {
+        while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+            let inters = l.intersection(r);
+
+            // Move forward the interval that ends first, the other one might
+            // still intersect with the next intervals.
+            if l.upper <= r.upper {
+                left.next();
+            } else {
+                right.next();
+            }
+            if !inters.is_empty() {
+                return Some(inters);
+            }
+        }
+        None
+    })
+}
+
+/// Returns the values of left that are not in right.
+/// ```
+/// #  use rust_intervals::{interval, difference_iter};
+///    let left = [interval!(1, 3), interval!(8, 10)];
+///    let right = [interval!(2, 9)];
+///    assert_eq!(
+///        difference_iter(left, right).collect::<Vec<_>>(),
+///        [interval!(1, 2), interval!(9, 10)],
+///    );
+/// ```
+pub fn difference_iter<T, L, R>(
+    left: L,
+    right: R,
+) -> impl Iterator<Item = Interval<T>>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    L: IntoIterator<Item = Interval<T>>,
+    R: IntoIterator<Item = Interval<T>>,
+{
+    let mut left = coalesce(left);
+    let mut right = coalesce(right).peekable();
+
+    // The part of the current interval from left that hasn't been output yet
+    let mut current: Option<Interval<T>> = None;
+
+    ::core::iter::from_fn(move || 'outer: loop {
+        let mut rest = match current.take() {
+            Some(c) => c,
+            None => left.next()?,
+        };
+        while let Some(r) = right.peek() {
+            if r.strictly_left_of_interval(&rest) {
+                right.next();
+            } else if rest.strictly_left_of_interval(r) {
+                break;
+            } else {
+                match rest.difference(r) {
+                    Pair::One(p1) => rest = p1,
+                    Pair::Two(p1, p2) => {
+                        current = Some(p2);
+                        return Some(p1);
+                    }
+                }
+                if rest.is_empty() {
+                    continue 'outer;
+                }
+            }
+        }
+        return Some(rest);
+    })
+}
+
+/// Returns the union of any number of sorted iterators of intervals.
+/// ```
+/// #  use rust_intervals::{interval, merge_all};
+///    let iters = vec![
+///        vec![interval!(1, 3), interval!(10, 12)],
+///        vec![interval!(2, 5)],
+///        vec![interval!(5, 6), interval!(20, 30)],
+///    ];
+///    assert_eq!(
+///        merge_all(iters).collect::<Vec<_>>(),
+///        [interval!(1, 6), interval!(10, 12), interval!(20, 30)],
+///    );
+/// ```
+#[cfg(feature = "std")]
+pub fn merge_all<T, I, J>(iters: I) -> impl Iterator<Item = Interval<T>>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    I: IntoIterator<Item = J>,
+    J: IntoIterator<Item = Interval<T>>,
+{
+    let mut iters = iters
+        .into_iter()
+        .map(|it| it.into_iter().filter(|i| !i.is_empty()))
+        .collect::<Vec<_>>();
+
+    // The heap contains the next interval of each iterator, so that finding
+    // the left-most one is O(log k) for k iterators.
+    let mut heap = iters
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(idx, it)| {
+            Some(HeapEntry {
+                intv: it.next()?,
+                idx,
+            })
+        })
+        .collect::<BinaryHeap<_>>();
+
+    coalesce(::core::iter::from_fn(move || {
+        let HeapEntry { intv, idx } = heap.pop()?;
+        if let Some(next) = iters.get_mut(idx).and_then(Iterator::next) {
+            heap.push(HeapEntry { intv: next, idx });
+        }
+        Some(intv)
+    }))
+}
+
+/// An interval in the heap used by [`merge_all()`], along with the index of
+/// the iterator it comes from.  The order is reversed, so that the max-heap
+/// returns the interval with the smallest lower bound first.
+#[cfg(feature = "std")]
+struct HeapEntry<T> {
+    intv: Interval<T>,
+    idx: usize,
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + NothingBetween> Ord for HeapEntry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Empty intervals were filtered out, and those that cannot be
+        // compared (NaN) are empty, so the lower bounds are comparable.
+        other
+            .intv
+            .lower
+            .partial_cmp(&self.intv.lower)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + NothingBetween> PartialOrd for HeapEntry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + NothingBetween> PartialEq for HeapEntry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: PartialOrd + NothingBetween> Eq for HeapEntry<T> {}
+
+/// The iterator returned by [`coalesce()`]
+pub struct Coalesce<T, I> {
+    iter: I,
+    current: Option<Interval<T>>,
+}
+
+impl<T, I> Iterator for Coalesce<T, I>
+where
+    T: PartialOrd + NothingBetween + Clone,
+    I: Iterator<Item = Interval<T>>,
+{
+    type Item = Interval<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for intv in self.iter.by_ref() {
+            if intv.is_empty() {
+                continue;
+            }
+            match self.current.take() {
+                None => self.current = Some(intv),
+                Some(c) => {
+                    if c.contiguous(&intv) {
+                        self.current = Some(c.convex_hull(&intv));
+                    } else {
+                        self.current = Some(intv);
+                        return Some(c);
+                    }
+                }
+            }
+        }
+        self.current.take()
+    }
+}
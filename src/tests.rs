@@ -20,6 +20,42 @@ pub(crate) mod test {
         }
     }
 
+    // Strings do not implement Bounded, which is needed for parsing
+    #[cfg(feature = "std")]
+    #[derive(Clone, Debug, PartialEq, PartialOrd)]
+    struct Word(String);
+    #[cfg(feature = "std")]
+    impl NothingBetween for Word {
+        fn nothing_between(&self, _other: &Self) -> bool {
+            false
+        }
+    }
+    #[cfg(feature = "std")]
+    impl Bounded for Word {
+        fn min_value() -> Self {
+            Word(String::new())
+        }
+        fn max_value() -> Self {
+            Word(String::from(char::MAX))
+        }
+    }
+    #[cfg(feature = "std")]
+    impl ::core::fmt::Display for Word {
+        fn fmt(
+            &self,
+            f: &mut ::core::fmt::Formatter<'_>,
+        ) -> ::core::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    #[cfg(feature = "std")]
+    impl ::core::str::FromStr for Word {
+        type Err = ::core::convert::Infallible;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Word(String::from(s)))
+        }
+    }
+
     /// Compares the positions of an interval and a value
     macro_rules! assert_lr {
         ($intv:expr, $v:expr,
@@ -438,6 +474,61 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn test_length() {
+        assert_eq!(interval!(1, 5, "[)").length(), Some(4_u32));
+        assert_eq!(interval!(1, 5, "[]").length(), Some(5_u32));
+        assert_eq!(interval!(1, 5, "()").length(), Some(3_u32));
+        assert_eq!(interval!(-5, 5, "(]").length(), Some(10_u32));
+        assert_eq!(interval!(5, 1, "[]").length(), Some(0_u32));
+        assert_eq!(interval!("-inf", 5).length(), None);
+        assert_eq!(interval!(1, "inf").length(), None);
+        assert_eq!(Interval::<i8>::doubly_unbounded().length(), None);
+        assert_eq!(interval!(i8::MIN, i8::MAX, "[)").length(), Some(255_u8));
+        assert_eq!(interval!(i8::MIN, i8::MAX, "[]").length(), None);
+        assert_eq!(interval!(i8::MIN, i8::MAX, "(]").length(), Some(255_u8));
+        assert_eq!(interval!(1.0, 2.5, "()").length(), Some(1.5));
+
+        #[cfg(feature = "std")]
+        {
+            use std::time::Duration;
+            let d1 = Duration::from_secs(1);
+            let d2 = Duration::from_secs(3);
+            assert_eq!(interval!(d1, d2).length(), Some(d2 - d1));
+            assert_eq!(
+                interval!(d1, d2, "[]").length(),
+                Some(d2 - d1 + Duration::from_nanos(1)),
+            );
+        }
+    }
+
+    #[test]
+    fn test_cardinality() {
+        assert_eq!(interval!(1, 5, "[)").cardinality(), Some(4));
+        assert_eq!(interval!(1, 5, "[]").cardinality(), Some(5));
+        assert_eq!(interval!(1, 5, "()").cardinality(), Some(3));
+        assert_eq!(interval!(1, 5, "(]").cardinality(), Some(4));
+        assert_eq!(interval!(1, 2, "()").cardinality(), Some(0));
+        assert_eq!(Interval::<u8>::doubly_unbounded().cardinality(), Some(256));
+        assert_eq!(interval!(0_u8, "(inf").cardinality(), Some(255));
+        assert_eq!(interval!("-inf", 0_i8, ")").cardinality(), Some(128));
+        assert_eq!(
+            Interval::<i64>::doubly_unbounded().cardinality(),
+            Some(1 << 64),
+        );
+        assert_eq!(
+            Interval::<usize>::doubly_unbounded().cardinality(),
+            Some(u128::try_from(usize::MAX).unwrap_or(0) + 1),
+        );
+        assert_eq!(
+            interval!(i128::MIN, i128::MAX, "[)").cardinality(),
+            Some(u128::MAX),
+        );
+        assert_eq!(interval!(i128::MIN, i128::MAX, "[]").cardinality(), None);
+        assert_eq!(Interval::<i128>::doubly_unbounded().cardinality(), None);
+        assert_eq!(interval!(0_u128, "(inf").cardinality(), Some(u128::MAX),);
+    }
+
     #[test]
     fn test_single() {
         let intv = Interval::new_single(4);
@@ -528,14 +619,366 @@ pub(crate) mod test {
             Interval::doubly_unbounded()
         );
         assert_eq!("empty".parse::<Interval<i32>>()?, Interval::empty());
+        assert_eq!("".parse::<Interval<i32>>()?, Interval::empty());
         assert_eq!(
             "&1,2".parse::<Interval<i32>>(),
             Err(ParseError::InvalidInput),
         );
         assert!("&1,2".parse::<Interval<i32>>().is_err());
+        assert!(matches!(
+            "[1, a)".parse::<Interval<i32>>(),
+            Err(ParseError::Bound(_)),
+        ));
+        Ok(())
+    }
+
+    /// PostgreSQL text format
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_postgres() -> Result<(), ParseError<::core::num::ParseIntError>> {
+        assert_eq!(
+            Interval::<u32>::parse_postgres(" [1,4] ")?,
+            interval!(1, 4, "[]")
+        );
+        assert_eq!(
+            Interval::<u32>::parse_postgres(" EMPTY ")?,
+            Interval::empty()
+        );
+        assert_eq!(
+            Interval::<u32>::parse_postgres("[,4]")?,
+            interval!("-inf", 4, "]")
+        );
+        assert_eq!(
+            Interval::<u32>::parse_postgres("[1,]")?,
+            interval!(1, "[inf")
+        );
+        assert_eq!(
+            Interval::<u32>::parse_postgres(r#"["1","4")"#)?,
+            interval!(1, 4, "[)"),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_postgres(r#"[1\0,4"0")"#)?,
+            interval!(10, 40, "[)"),
+        );
+
+        // infinity is a value, not a keyword
+        assert!(matches!(
+            Interval::<i32>::parse_postgres("[-infinity,4)")
+                .map_err(|e| (e.offset(), e.into_error())),
+            Err((1, ParseError::Bound(_))),
+        ));
+        let intv1 = Interval::<f64>::parse_postgres("[1,Infinity]")
+            .map_err(|_| ParseError::InvalidInput)?;
+        assert_eq!(intv1, Interval::new_closed_closed(1.0, f64::INFINITY));
+        assert!(!intv1.upper_unbounded());
+        assert!(intv1.upper_inclusive());
+        assert_eq!(
+            Interval::<f64>::parse_postgres(r#"("-infinity",0)"#),
+            Ok(Interval::new_open_open(f64::NEG_INFINITY, 0.0)),
+        );
+
+        // Errors
+        let invalid = |s: &str| {
+            Interval::<u32>::parse_postgres(s).map_err(|e| e.offset())
+        };
+        assert_eq!(
+            Interval::<u32>::parse_postgres(""),
+            Err(ParseErrorAt::invalid(0)),
+        );
+        assert_eq!(invalid("1,4]"), Err(0));
+        assert_eq!(invalid("[1,4"), Err(4));
+        assert_eq!(invalid("[1,4,5]"), Err(4));
+        assert_eq!(invalid("[1,4] x"), Err(6));
+        assert_eq!(invalid("empty x"), Err(6));
+        assert_eq!(invalid(r#"[1,"4)"#), Err(6));
+        assert!(matches!(
+            Interval::<u32>::parse_postgres("[1,a)")
+                .map_err(|e| (e.offset(), e.into_error())),
+            Err((3, ParseError::Bound(_))),
+        ));
+        assert!(matches!(
+            Interval::<u32>::parse_postgres("[1,a)").map_err(ParseError::from),
+            Err(ParseError::Bound(_)),
+        ));
+
+        // Whitespace within the brackets is part of the bounds
+        assert_eq!(invalid("[1, 4)"), Err(3));
+        assert_eq!(invalid(r#"["1" ,4)"#), Err(1));
+        assert_eq!(invalid("[ ,4)"), Err(1));
+        assert_eq!(
+            Interval::<char>::parse_postgres("( ,a]"),
+            Ok(Interval::new_open_closed(' ', 'a')),
+        );
+        assert_eq!(
+            Interval::<char>::parse_postgres("(,a]"),
+            Ok(Interval::new_unbounded_closed('a')),
+        );
+        assert_eq!(
+            Interval::<char>::parse_postgres(r#"(" ",a]"#),
+            Ok(Interval::new_open_closed(' ', 'a')),
+        );
+        assert!(Interval::<char>::parse_postgres(r#"(" ", a]"#).is_err());
+
+        // Special characters are quoted
+        assert_eq!(interval!(1, 4).postgres().to_string(), "[1,4)");
+        assert_eq!(interval!(1, 4, "(]").postgres().to_string(), "(1,4]");
+        assert_eq!(interval!(1, "[inf").postgres().to_string(), "[1,)");
+        assert_eq!(interval!("-inf", 4, "]").postgres().to_string(), "(,4]");
+        assert_eq!(
+            Interval::<u32>::doubly_unbounded().postgres().to_string(),
+            "(,)"
+        );
+        assert_eq!(Interval::<u32>::empty().postgres().to_string(), "empty");
+        assert_eq!(
+            Interval::new_closed_closed(' ', 'a').postgres().to_string(),
+            r#"[" ",a]"#,
+        );
+        assert_eq!(
+            Interval::new_closed_closed('"', '\\')
+                .postgres()
+                .to_string(),
+            r#"["""","\\"]"#,
+        );
+
+        // Infinite values and keywords round-trip
+        for intv in [
+            Interval::new_closed_closed(1.0, f64::INFINITY),
+            Interval::new_open_closed(f64::NEG_INFINITY, 1.0),
+            Interval::new_closed_unbounded(f64::INFINITY),
+        ] {
+            assert_eq!(
+                Interval::parse_postgres(&intv.postgres().to_string()),
+                Ok(intv),
+            );
+        }
+        let word = |w: &str| Word(String::from(w));
+        let keywords =
+            Interval::new_closed_closed(word("-Infinity"), word("infinity"));
+        assert_eq!(
+            keywords.postgres().to_string(),
+            r#"["-Infinity","infinity"]"#,
+        );
+        assert_eq!(
+            Interval::parse_postgres(&keywords.postgres().to_string()),
+            Ok(keywords),
+        );
+        assert_eq!(
+            Interval::parse_postgres("[-infinity,infinity]"),
+            Ok(Interval::new_closed_closed(
+                word("-infinity"),
+                word("infinity")
+            )),
+        );
+        let words = Interval::new_closed_open(word("empty"), word("infinityx"));
+        assert_eq!(words.postgres().to_string(), r#"["empty",infinityx)"#);
+        assert_eq!(
+            Interval::parse_postgres(&words.postgres().to_string()),
+            Ok(words),
+        );
+
+        let chars = [' ', '"', '(', ')', ',', '[', '\\', ']', 'a'];
+        for lo in chars {
+            for up in chars {
+                let intv = Interval::new_closed_open(lo, up);
+                assert_eq!(
+                    Interval::parse_postgres(&intv.postgres().to_string()),
+                    Ok(intv),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Alternative notations
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_notation() -> Result<(), ParseError<::core::num::ParseIntError>> {
+        let intvs = [
+            (interval!(1, 5, "[)"), "1..5", "[1, 5[", r"1 \le x < 5"),
+            (interval!(1, 5, "[]"), "1..=5", "[1, 5]", r"1 \le x \le 5"),
+            (interval!(1, 5, "()"), "(1, 5)", "]1, 5[", r"1 < x < 5"),
+            (interval!(1, 5, "(]"), "(1, 5]", "]1, 5]", r"1 < x \le 5"),
+            (interval!(1, "[inf"), "1..", "[1, +∞[", r"1 \le x < \infty"),
+            (interval!(1, "(inf"), "(1,)", "]1, +∞[", r"1 < x < \infty"),
+            (
+                interval!("-inf", 5, ")"),
+                "..5",
+                "]-∞, 5[",
+                r"-\infty < x < 5",
+            ),
+            (
+                interval!("-inf", 5, "]"),
+                "..=5",
+                "]-∞, 5]",
+                r"-\infty < x \le 5",
+            ),
+            (
+                Interval::doubly_unbounded(),
+                "..",
+                "]-∞, +∞[",
+                r"-\infty < x < \infty",
+            ),
+        ];
+        for (intv, rust, iso, set) in intvs {
+            let set = format!(r"\{{x \mid {}\}}", set);
+            for (notation, expected) in [
+                (Notation::Rust, rust),
+                (Notation::Iso31_11, iso),
+                (Notation::SetBuilder, &set),
+            ] {
+                assert_eq!(intv.display(notation).to_string(), expected);
+                assert_eq!(Interval::parse_with(expected, notation)?, intv);
+            }
+            for notation in [Notation::Default, Notation::Postgres] {
+                assert_eq!(
+                    Interval::parse_with(
+                        &intv.display(notation).to_string(),
+                        notation
+                    )?,
+                    intv,
+                );
+            }
+        }
+
+        let empty = Interval::<i32>::empty();
+        assert_eq!(empty.display(Notation::Rust).to_string(), "empty");
+        assert_eq!(empty.display(Notation::Iso31_11).to_string(), "∅");
+        assert_eq!(
+            empty.display(Notation::SetBuilder).to_string(),
+            r"\emptyset"
+        );
+        for (s, notation) in [
+            ("empty", Notation::Rust),
+            ("∅", Notation::Iso31_11),
+            (r"\emptyset", Notation::SetBuilder),
+        ] {
+            assert!(Interval::<i32>::parse_with(s, notation)?.is_empty());
+        }
+        assert_eq!(Notation::default(), Notation::Default);
+
+        // Variations in the input
+        assert_eq!(
+            Interval::parse_with(" -3 ..= -1 ", Notation::Rust)?,
+            interval!(-3, -1, "[]"),
+        );
+        assert_eq!(
+            Interval::parse_with("[1,]", Notation::Iso31_11)?,
+            interval!(1, "[inf"),
+        );
+        assert_eq!(
+            Interval::parse_with(
+                r"\{ x \mid 1\leq x<5 \}",
+                Notation::SetBuilder
+            )?,
+            interval!(1, 5, "[)"),
+        );
+
+        // Errors
+        assert_eq!(
+            Interval::<u32>::parse_with("1.5", Notation::Rust),
+            Err(ParseErrorAt::invalid(3)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with("1..=", Notation::Rust),
+            Err(ParseErrorAt::invalid(4)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with("1..a", Notation::Rust)
+                .map_err(|e| e.offset()),
+            Err(3),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with("(1, 5[", Notation::Iso31_11),
+            Err(ParseErrorAt::invalid(0)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with("[1, 5)", Notation::Iso31_11),
+            Err(ParseErrorAt::invalid(6)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with("[1 5]", Notation::Iso31_11),
+            Err(ParseErrorAt::invalid(4)),
+        );
+        assert!(matches!(
+            Interval::<u32>::parse_with("[1, b]", Notation::Iso31_11)
+                .map_err(|e| (e.offset(), e.into_error())),
+            Err((4, ParseError::Bound(_))),
+        ));
+        assert_eq!(
+            Interval::<u32>::parse_with(
+                r"\{y \mid 1 < y\}",
+                Notation::SetBuilder
+            ),
+            Err(ParseErrorAt::invalid(0)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with(
+                r"\{x \mid 1 < x",
+                Notation::SetBuilder
+            ),
+            Err(ParseErrorAt::invalid(14)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with(
+                r"\{x \mid 1 < 5\}",
+                Notation::SetBuilder
+            ),
+            Err(ParseErrorAt::invalid(13)),
+        );
+        assert_eq!(
+            Interval::<u32>::parse_with(
+                r"\{x \mid 1 < x = 5\}",
+                Notation::SetBuilder
+            ),
+            Err(ParseErrorAt::invalid(15)),
+        );
         Ok(())
     }
 
+    /// ParseError implements the Error trait
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_error() {
+        use ::std::error::Error;
+        let err = "[1, a)".parse::<Interval<u32>>().err();
+        assert_eq!(
+            err.as_ref().map(|e| e.to_string()),
+            Some("invalid bound: invalid digit found in string".into()),
+        );
+        assert!(err.as_ref().and_then(|e| e.source()).is_some());
+
+        let err = "[1, 2".parse::<Interval<u32>>().err();
+        assert_eq!(
+            err.as_ref().map(|e| e.to_string()),
+            Some("invalid input".into()),
+        );
+        assert!(err.as_ref().and_then(|e| e.source()).is_none());
+
+        let err = "{[1, 2), [a, 5)}".parse::<IntervalSet<u32>>().err();
+        assert_eq!(
+            err.map(|e| e.to_string()),
+            Some(
+                "invalid bound in interval #1: invalid digit found in string"
+                    .into()
+            ),
+        );
+
+        let err = Interval::<u32>::parse_postgres("[1,a)").err();
+        assert_eq!(
+            err.as_ref().map(|e| e.to_string()),
+            Some(
+                "invalid bound at byte 3: invalid digit found in string".into()
+            ),
+        );
+        assert!(err.as_ref().and_then(|e| e.source()).is_some());
+
+        let err = Interval::<u32>::parse_with("[1, 2", Notation::Default).err();
+        assert_eq!(
+            err.map(|e| e.to_string()),
+            Some("invalid input at byte 5".into()),
+        );
+    }
+
     /// Test From<Interval<T>> -> String
     #[cfg(feature = "std")]
     #[test]
@@ -564,6 +1007,255 @@ pub(crate) mod test {
         Ok(())
     }
 
+    /// Test conversions with core::ops::Bound and RangeBounds
+    #[test]
+    fn test_std_bounds() {
+        use ::core::ops::{Bound as RgBound, RangeBounds};
+        let all = [
+            (
+                interval!(1, 4, "[]"),
+                RgBound::Included(1),
+                RgBound::Included(4),
+            ),
+            (
+                interval!(1, 4, "[)"),
+                RgBound::Included(1),
+                RgBound::Excluded(4),
+            ),
+            (
+                interval!(1, 4, "(]"),
+                RgBound::Excluded(1),
+                RgBound::Included(4),
+            ),
+            (
+                interval!(1, 4, "()"),
+                RgBound::Excluded(1),
+                RgBound::Excluded(4),
+            ),
+            (
+                interval!("-inf", 4, "]"),
+                RgBound::Unbounded,
+                RgBound::Included(4),
+            ),
+            (
+                interval!("-inf", 4, ")"),
+                RgBound::Unbounded,
+                RgBound::Excluded(4),
+            ),
+            (
+                interval!(1, "[inf"),
+                RgBound::Included(1),
+                RgBound::Unbounded,
+            ),
+            (
+                interval!(1, "(inf"),
+                RgBound::Excluded(1),
+                RgBound::Unbounded,
+            ),
+            (
+                Interval::doubly_unbounded(),
+                RgBound::Unbounded,
+                RgBound::Unbounded,
+            ),
+        ];
+        for (intv, lo, up) in all {
+            assert_eq!(intv.bounds(), Some((lo.as_ref(), up.as_ref())));
+            assert_eq!(intv.start_bound(), lo.as_ref());
+            assert_eq!(intv.end_bound(), up.as_ref());
+            assert_eq!((&intv).start_bound(), lo.as_ref());
+            assert_eq!((&intv).end_bound(), up.as_ref());
+            assert_eq!(
+                Interval::from_bounds(intv.lower_bound(), intv.upper_bound()),
+                intv
+            );
+            assert_eq!(Interval::from((lo, up)), intv);
+            assert_eq!(Interval::from_range((lo, up)), intv);
+            assert_eq!(intv.into_bounds(), Some((lo, up)));
+        }
+
+        assert_eq!(Interval::<u8>::empty().bounds(), None);
+        assert_eq!(Interval::<u8>::empty().into_bounds(), None);
+        assert!(Interval::from((RgBound::Included(4), RgBound::Excluded(4)))
+            .is_empty());
+        assert!(Interval::from((
+            RgBound::Excluded(u8::MAX),
+            RgBound::Unbounded
+        ))
+        .is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_std_bounds_panic() {
+        use ::core::ops::RangeBounds;
+        let _ = Interval::<u8>::empty().start_bound();
+    }
+
+    #[test]
+    fn test_public_bounds() {
+        let empty = Interval::<u8>::empty();
+        assert!(matches!(empty.lower_bound(), Bound::RightUnbounded));
+        assert!(matches!(empty.upper_bound(), Bound::LeftUnbounded));
+        let intv1 = interval!(1, 4, "(]");
+        assert!(matches!(intv1.lower_bound(), Bound::RightOf(1)));
+        assert!(matches!(intv1.upper_bound(), Bound::RightOf(4)));
+        assert_eq!(intv1.lower_bound().value(), Some(&1));
+        assert_eq!(Bound::<u8>::LeftUnbounded.value(), None);
+        assert!(matches!(
+            interval!("-inf", 4, ")").lower_bound().as_ref(),
+            Bound::LeftUnbounded,
+        ));
+        assert_eq!(
+            Interval::from_bounds(&Bound::LeftOf(4), &Bound::RightOf(4)),
+            interval!(4, 4, "[]"),
+        );
+        assert!(Interval::from_bounds(&Bound::RightOf(4), &Bound::LeftOf(4))
+            .is_empty());
+        assert!(Interval::from_bounds(
+            &Bound::<u8>::RightUnbounded,
+            &Bound::RightUnbounded
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(Interval::try_new_closed_open(1, 5), Ok(interval!(1, 5)));
+        assert_eq!(
+            Interval::try_new_closed_closed(1, 5),
+            Ok(interval!(1, 5, "[]")),
+        );
+        assert_eq!(
+            Interval::try_new_open_open(1, 5),
+            Ok(interval!(1, 5, "()"))
+        );
+        assert_eq!(
+            Interval::try_new_open_closed(1, 5),
+            Ok(interval!(1, 5, "(]")),
+        );
+        assert_eq!(
+            Interval::try_new_unbounded_closed(5),
+            Ok(interval!("-inf", 5, "]")),
+        );
+        assert_eq!(
+            Interval::try_new_unbounded_open(5),
+            Ok(interval!("-inf", 5, ")")),
+        );
+        assert_eq!(
+            Interval::try_new_closed_unbounded(5),
+            Ok(interval!(5, "[inf")),
+        );
+        assert_eq!(
+            Interval::try_new_open_unbounded(5),
+            Ok(interval!(5, "(inf")),
+        );
+
+        // Equal bounds are valid, though the interval might be empty
+        assert_eq!(Interval::try_new_closed_open(5, 5), Ok(Interval::empty()));
+        assert_eq!(Interval::try_new_open_open(5, 6), Ok(Interval::empty()));
+        assert_eq!(
+            Interval::try_new_closed_closed(5, 5),
+            Ok(interval!(5, 5, "[]")),
+        );
+        assert_eq!(
+            Interval::try_new_open_unbounded(u8::MAX),
+            Ok(Interval::empty()),
+        );
+
+        assert_eq!(
+            Interval::try_new_closed_open(5, 1),
+            Err(BoundsError::Inverted),
+        );
+        assert_eq!(
+            Interval::try_new_open_closed(5.0, 1.0),
+            Err(BoundsError::Inverted),
+        );
+        assert_eq!(
+            Interval::try_new_closed_open(f64::NAN, 1.0),
+            Err(BoundsError::Unordered),
+        );
+        assert_eq!(
+            Interval::try_new_open_open(1.0, f32::NAN),
+            Err(BoundsError::Unordered),
+        );
+        assert_eq!(
+            Interval::try_new_closed_unbounded(f32::NAN),
+            Err(BoundsError::Unordered),
+        );
+        assert_eq!(
+            Interval::try_new_closed_closed(1.0, f64::INFINITY),
+            Err(BoundsError::Infinite),
+        );
+        assert_eq!(
+            Interval::try_new_unbounded_open(f64::NEG_INFINITY),
+            Err(BoundsError::Infinite),
+        );
+        assert_eq!(
+            Interval::try_new_closed_closed(f64::MIN, f64::MAX),
+            Ok(interval!(f64::MIN, f64::MAX, "[]")),
+        );
+
+        assert_eq!(Interval::try_from_range(1..=5), Ok(interval!(1, 5, "[]")));
+        assert_eq!(
+            Interval::<u8>::try_from_range(..),
+            Ok(Interval::doubly_unbounded())
+        );
+        assert_eq!(Interval::try_from_range(5..1), Err(BoundsError::Inverted));
+        assert_eq!(
+            Interval::try_from_range(f32::NAN..),
+            Err(BoundsError::Unordered),
+        );
+    }
+
+    #[test]
+    fn test_nan_bounds() {
+        // Intervals with NaN bounds can still be created by applying
+        // functions, but comparing them must not panic
+        let intv1 = interval!(1.0, "inf").map(|_| f64::NAN);
+        let intv2 = interval!(1.0, 2.0);
+        assert_eq!(intv1.partial_cmp(&intv2), None);
+        assert_eq!(intv2.partial_cmp(&intv1), None);
+        assert!(!(intv1 < intv2));
+        assert!(!(intv1 > intv2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_bounds_error() {
+        assert_eq!(
+            BoundsError::Inverted.to_string(),
+            "lower bound is greater than upper bound",
+        );
+        assert_eq!(
+            BoundsError::Unordered.to_string(),
+            "bound cannot be compared"
+        );
+        assert_eq!(BoundsError::Infinite.to_string(), "bound is not finite");
+        let err: Box<dyn ::std::error::Error> = Box::new(BoundsError::Infinite);
+        assert!(err.source().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_range_bounds() {
+        let mut v = vec![0, 1, 2, 3, 4, 5];
+        assert_eq!(v.drain(interval!(1, 3, "(]")).collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(v, [0, 1, 4, 5]);
+
+        let map = ::std::collections::BTreeMap::from([(1, 'a'), (5, 'b')]);
+        let intv1 = interval!(1, "(inf");
+        assert_eq!(map.range(&intv1).collect::<Vec<_>>(), [(&5, &'b')]);
+        assert_eq!(map.range(interval!("-inf", 5)).count(), 1);
+        assert_eq!(map.range(Interval::doubly_unbounded()).count(), 2);
+
+        // Empty intervals have no bounds, so use bounds() to avoid a panic
+        assert_eq!(
+            Interval::empty().bounds().map(|b| map.range(b).count()),
+            None,
+        );
+        assert_eq!(intv1.bounds().map(|b| map.range(b).count()), Some(1),);
+    }
+
     /// Test Display and ToString traits
     #[cfg(feature = "std")]
     #[test]
@@ -945,17 +1637,156 @@ pub(crate) mod test {
     }
 
     #[test]
-    fn test_intersection() {
-        let intv1 = Interval::new_closed_closed(10_u8, 30);
-        let intv2 = Interval::new_closed_open(40_u8, 50);
-        let intv3 = Interval::new_open_unbounded(35_u8);
-        let empty = Interval::empty();
-        assert!(!intv1.intersects(intv2));
-        assert_eq!(intv1.intersection(intv2), empty.clone());
-        assert!(intv2.intersects(intv3));
+    fn test_distance() {
+        let intv1 = interval!(10_u32, 20, "[]");
+        assert_eq!(intv1.distance_to(interval!(1, 5)), Some(5));
+        assert_eq!(intv1.distance_to(interval!(1, 10)), Some(0));
+        assert_eq!(intv1.distance_to(interval!(1, 9, "[]")), Some(0));
+        assert_eq!(intv1.distance_to(interval!(15, 30)), Some(0));
+        assert_eq!(intv1.distance_to(interval!(22, "inf")), Some(1));
+        assert_eq!(intv1.distance_to(interval!("-inf", 1, "]")), Some(8));
+        assert_eq!(intv1.distance_to(Interval::empty()), None);
+        assert_eq!(Interval::empty().distance_to(intv1), None);
         assert_eq!(
-            intv2.intersection(intv3),
-            Interval::new_closed_open(40, 50)
+            interval!(i8::MIN, i8::MIN, "[]").distance_to(interval!(
+                i8::MAX,
+                i8::MAX,
+                "[]"
+            )),
+            Some(254),
+        );
+
+        assert_eq!(intv1.distance_to_value(&25), Some(4));
+        assert_eq!(intv1.distance_to_value(&21), Some(0));
+        assert_eq!(intv1.distance_to_value(&15), Some(0));
+        assert_eq!(intv1.distance_to_value(&0), Some(9));
+        assert_eq!(Interval::empty().distance_to_value(&0), None::<u32>);
+
+        let intv2 = interval!(1.0, 3.0, "()");
+        assert_eq!(intv2.distance_to(interval!(3.0, 8.0, "[]")), Some(0.0));
+        assert_eq!(intv2.distance_to(interval!(4.0, 8.0, "[]")), Some(1.0));
+        assert_eq!(intv2.distance_to_value(&0.5), Some(0.5));
+        assert_eq!(intv2.distance_to_value(&1.0), Some(0.0));
+    }
+
+    #[test]
+    fn test_nearest_value() {
+        let intv1 = interval!(10, 20, "()");
+        assert_eq!(intv1.nearest_value(&15), Some(15));
+        assert_eq!(intv1.nearest_value(&10), Some(11));
+        assert_eq!(intv1.nearest_value(&0), Some(11));
+        assert_eq!(intv1.nearest_value(&20), Some(19));
+        assert_eq!(intv1.nearest_value(&100), Some(19));
+
+        let intv2 = interval!(10, 20, "[]");
+        assert_eq!(intv2.nearest_value(&0), Some(10));
+        assert_eq!(intv2.nearest_value(&100), Some(20));
+        assert_eq!(interval!(10, "inf").nearest_value(&100), Some(100));
+        assert_eq!(interval!("-inf", 10).nearest_value(&100), Some(9));
+        assert_eq!(
+            Interval::<u8>::doubly_unbounded().nearest_value(&3),
+            Some(3)
+        );
+        assert_eq!(Interval::empty().nearest_value(&3), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_canonicalize() {
+        let intv1 = interval!(1, 4);
+        assert_eq!(interval!(1, 3, "[]").canonicalize().to_string(), "[1, 4)");
+        assert_eq!(interval!(0, 4, "()").canonicalize().to_string(), "[1, 4)");
+        assert_eq!(interval!(0, 3, "(]").canonicalize().to_string(), "[1, 4)");
+        assert_eq!(intv1.canonicalize().to_string(), "[1, 4)");
+        assert_eq!(interval!(0, 3, "(]").canonicalize(), intv1);
+        assert_eq!(interval!(0, "(inf").canonicalize().to_string(), "[1,)");
+        assert_eq!(
+            interval!("-inf", 3, "]").canonicalize().to_string(),
+            "(, 4)"
+        );
+        assert_eq!(
+            Interval::<i8>::doubly_unbounded()
+                .canonicalize()
+                .to_string(),
+            "(,)",
+        );
+        assert_eq!(
+            interval!(i8::MIN, i8::MAX, "[]").canonicalize().to_string(),
+            "[-128, 127]",
+        );
+        assert!(Interval::<u8>::empty().canonicalize().is_empty());
+        assert!(interval!(3, 3, "()").canonicalize().is_empty());
+
+        // Characters skip the surrogate range
+        assert_eq!(
+            interval!('a', 'c', "[]").canonicalize().to_string(),
+            "[a, d)",
+        );
+        let intv2 = interval!('\u{D7FF}', '\u{E000}', "(]");
+        assert_eq!(intv2.canonicalize(), interval!('\u{E000}', '\u{E001}'));
+        assert_eq!(intv2.cardinality(), Some(1));
+        assert!(interval!('\u{D7FF}', '\u{E000}', "()").is_empty());
+        assert_eq!('\u{E000}'.backward(1), Some('\u{D7FF}'));
+        assert_eq!(char::MAX.forward(1), None);
+        assert_eq!('\0'.backward(1), None);
+
+        assert_eq_and_hash(
+            &interval!(1, 3, "[]").canonicalize(),
+            &interval!(0, 4, "()").canonicalize(),
+        );
+    }
+
+    #[test]
+    fn test_map_bounds() {
+        let intv1 = interval!(1, 5, "(]");
+        assert_eq!(intv1.map(|v| v * 1000), interval!(1000, 5000, "(]"));
+        assert_eq!(intv1.map(|v| v * 1000).upper(), Some(&5000));
+        assert!(intv1.map(|v| v * 1000).upper_inclusive());
+        assert!(!intv1.map(|v| v * 1000).lower_inclusive());
+        assert_eq!(
+            interval!("-inf", 5_u8).map(|v| u32::from(*v)),
+            interval!("-inf", 5_u32),
+        );
+        assert_eq!(
+            Interval::<u8>::doubly_unbounded().map(|v| u32::from(*v)),
+            Interval::<u32>::doubly_unbounded(),
+        );
+        assert!(Interval::<u8>::empty().map(|v| u32::from(*v)).is_empty());
+
+        // Functions that are not strictly increasing
+        assert!(interval!(1, 5).map(|_| 3).is_empty());
+        assert_eq!(
+            interval!(1, 5, "[]").map(|v| v / 10),
+            interval!(0, 0, "[]")
+        );
+
+        let intv2 = interval!(1, 5, "[)");
+        assert_eq!(intv2.map_decreasing(|v| -v), interval!(-5, -1, "(]"));
+        assert!(intv2.map_decreasing(|v| -v).upper_inclusive());
+        assert_eq!(
+            interval!(1, "(inf").map_decreasing(|v| 10 - v),
+            interval!("-inf", 9, ")"),
+        );
+        assert_eq!(
+            interval!("-inf", 4, "]").map_decreasing(|v| -v),
+            interval!(-4, "[inf"),
+        );
+        assert!(Interval::<i8>::empty().map_decreasing(|v| -v).is_empty());
+        assert!(interval!(1, 5).map_decreasing(|_| 3).is_empty());
+    }
+
+    #[test]
+    fn test_intersection() {
+        let intv1 = Interval::new_closed_closed(10_u8, 30);
+        let intv2 = Interval::new_closed_open(40_u8, 50);
+        let intv3 = Interval::new_open_unbounded(35_u8);
+        let empty = Interval::empty();
+        assert!(!intv1.intersects(intv2));
+        assert_eq!(intv1.intersection(intv2), empty.clone());
+        assert!(intv2.intersects(intv3));
+        assert_eq!(
+            intv2.intersection(intv3),
+            Interval::new_closed_open(40, 50)
         );
     }
 
@@ -1005,6 +1836,473 @@ pub(crate) mod test {
         );
     }
 
+    #[test]
+    fn test_complement() {
+        let intv1 = Interval::new_closed_closed(10_u8, 30);
+        assert_eq!(
+            intv1.complement(),
+            Pair::Two(
+                Interval::new_unbounded_open(10),
+                Interval::new_open_unbounded(30),
+            ),
+        );
+        assert_eq!(!intv1, intv1.complement());
+        assert_eq!(
+            Interval::new_closed_closed(0_u8, 255).complement(),
+            Pair::One(Interval::empty()),
+        );
+        assert_eq!(
+            Interval::new_closed_open(0_u8, 30).complement(),
+            Pair::One(Interval::new_closed_unbounded(30)),
+        );
+        assert_eq!(
+            Interval::new_open_unbounded(30_u8).complement(),
+            Pair::One(Interval::new_unbounded_closed(30)),
+        );
+        assert_eq!(
+            Interval::<u8>::empty().complement(),
+            Pair::One(Interval::doubly_unbounded()),
+        );
+        assert_eq!(
+            Interval::<u8>::doubly_unbounded().complement(),
+            Pair::One(Interval::empty()),
+        );
+
+        let intv2 = Interval::new_closed_open(1.0, 2.0);
+        assert_eq!(
+            intv2.complement(),
+            Pair::Two(
+                Interval::new_unbounded_open(1.0),
+                Interval::new_closed_unbounded(2.0),
+            ),
+        );
+
+        assert_eq!(
+            intv1.complement_within(Interval::new_closed_closed(0, 20)),
+            Pair::One(Interval::new_closed_open(0, 10)),
+        );
+        assert_eq!(
+            intv1.complement_within(Interval::new_closed_closed(0, 40)),
+            Pair::Two(
+                Interval::new_closed_open(0, 10),
+                Interval::new_open_closed(30, 40),
+            ),
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // Compare with the result of applying the operation on all values.
+        let small = [
+            Interval::empty(),
+            interval!(-3, 3, "[]"),
+            interval!(-3, 3, "()"),
+            interval!(-2, 0, "[]"),
+            interval!(-2, 0, "[)"),
+            interval!(0, 2, "(]"),
+            interval!(1, 3, "[]"),
+            interval!(-3, -1, "()"),
+            interval!(0, 0, "[]"),
+        ];
+        let hull = |op: &dyn Fn(i32, i32) -> Option<i32>,
+                    a: &Interval<i32>,
+                    b: &Interval<i32>| {
+            let mut values = (-10..=10)
+                .filter(|x| a.contains(x))
+                .flat_map(|x| {
+                    (-10..=10).filter(|y| b.contains(y)).map(move |y| (x, y))
+                })
+                .filter_map(|(x, y)| op(x, y));
+            match values.next() {
+                None => Interval::empty(),
+                Some(first) => {
+                    let (lo, hi) = values
+                        .fold((first, first), |(lo, hi), v| {
+                            (lo.min(v), hi.max(v))
+                        });
+                    interval!(lo, hi, "[]")
+                }
+            }
+        };
+        for a in &small {
+            for b in &small {
+                assert_eq!(
+                    a.checked_add(b),
+                    Some(hull(&|x, y| Some(x + y), a, b)),
+                    "{} + {}",
+                    a,
+                    b,
+                );
+                assert_eq!(
+                    a.checked_sub(b),
+                    Some(hull(&|x, y| Some(x - y), a, b)),
+                    "{} - {}",
+                    a,
+                    b,
+                );
+                assert_eq!(
+                    a.checked_mul(b),
+                    Some(hull(&|x, y| Some(x * y), a, b)),
+                    "{} * {}",
+                    a,
+                    b,
+                );
+                let neg = hull(&|x, y| (y < 0).then(|| x / y), a, b);
+                let pos = hull(&|x, y| (y > 0).then(|| x / y), a, b);
+                let quotient = a.checked_div(b);
+                for v in -10..=10 {
+                    let contained = match &quotient {
+                        Some(Pair::One(i)) => i.contains(v),
+                        Some(Pair::Two(i1, i2)) => {
+                            i1.contains(v) || i2.contains(v)
+                        }
+                        None => false,
+                    };
+                    assert_eq!(
+                        contained,
+                        neg.contains(v) || pos.contains(v),
+                        "{} / {} contains {}",
+                        a,
+                        b,
+                        v,
+                    );
+                }
+            }
+            assert_eq!(a.checked_neg(), Some(hull(&|x, _| Some(-x), a, a)));
+        }
+
+        // Unbounded intervals
+        let intv1 = interval!(1, 5, "[)");
+        assert_eq!(
+            intv1.checked_add(interval!("-inf", 3, "]")),
+            Some(interval!("-inf", 7, "]")),
+        );
+        assert_eq!(
+            intv1.checked_sub(interval!(3, "inf")),
+            Some(interval!("-inf", 1, "]")),
+        );
+        assert_eq!(
+            intv1.checked_mul(interval!(-1, "inf")),
+            Some(interval!(-4, "inf")),
+        );
+        assert_eq!(
+            intv1.checked_mul(Interval::doubly_unbounded()),
+            Some(Interval::doubly_unbounded()),
+        );
+        assert_eq!(
+            interval!(0, 0, "[]").checked_mul(Interval::doubly_unbounded()),
+            Some(interval!(0, 0, "[]")),
+        );
+        assert_eq!(-interval!(3, "(inf"), Some(interval!("-inf", -3, ")")),);
+
+        // Operators with scalars
+        assert_eq!(&intv1 + 1, Some(interval!(2, 6, "[)")));
+        assert_eq!(intv1.checked_sub_scalar(1), Some(interval!(0, 4, "[)")));
+        assert_eq!(
+            interval!(3, "inf").checked_sub_scalar(&5),
+            Some(interval!(-2, "inf")),
+        );
+        assert_eq!(
+            Interval::<i32>::empty().checked_sub_scalar(1),
+            Some(Interval::empty()),
+        );
+        // Value minus an interval, with open or unbounded bounds
+        assert_eq!(
+            Interval::new_single(10).checked_sub(interval!(3, 5, "()")),
+            Some(interval!(5, 7, "()")),
+        );
+        assert_eq!(
+            Interval::new_single(10).checked_sub(interval!(3, "(inf")),
+            Some(interval!("-inf", 7, ")")),
+        );
+        assert_eq!(
+            Interval::new_single(1.0).checked_sub(interval!("-inf", 0.5, ")")),
+            Some(interval!(0.5, "(inf")),
+        );
+        assert_eq!(
+            Interval::new_single(0_u8).checked_sub(interval!(0, 1, "[)")),
+            Some(interval!(0, 0, "[]")),
+        );
+        assert_eq!(
+            Interval::new_single(0).checked_sub(Interval::doubly_unbounded()),
+            Some(Interval::doubly_unbounded()),
+        );
+        assert_eq!(&intv1 * -2, Some(interval!(-8, -2, "[]")));
+        assert_eq!(&intv1 / 2, Some(Pair::One(interval!(0, 2, "[]"))),);
+        assert_eq!(&intv1 / 0, Some(Pair::One(Interval::empty())));
+        assert_eq!(-&intv1, Some(interval!(-4, -1, "[]")));
+
+        // Overflow
+        assert_eq!(interval!(1, i32::MAX, "[]") + 1, None);
+        assert_eq!(
+            interval!(1, i32::MAX, "[)") + 1,
+            Some(interval!(2, i32::MAX, "[]"))
+        );
+        assert_eq!(-interval!(i32::MIN, 0, "[]"), None);
+        assert_eq!(
+            interval!(i32::MIN, 0, "(]").checked_neg(),
+            Some(interval!(0, i32::MAX, "[]"))
+        );
+        assert_eq!(
+            interval!(1_u8, 2, "[]").checked_sub(interval!(2, 3, "[]")),
+            None
+        );
+        assert_eq!(interval!(1_u8, 20, "[]") * 20, None);
+
+        // Floats
+        let intv2 = interval!(1.0, 2.0, "[]");
+        assert_eq!(
+            intv2.checked_add(interval!(3.0, 4.0, "()")),
+            Some(interval!(4.0, 6.0, "()")),
+        );
+        assert_eq!(
+            intv2.checked_mul(interval!(0.0, "(inf")),
+            Some(interval!(0.0, "(inf")),
+        );
+        assert_eq!(
+            intv2 / interval!(-1.0, 1.0, "[]"),
+            Some(Pair::Two(
+                interval!("-inf", -1.0, "]"),
+                interval!(1.0, "[inf"),
+            )),
+        );
+        assert_eq!(
+            intv2 / interval!(0.0, 1.0, "[]"),
+            Some(Pair::One(interval!(1.0, "[inf"))),
+        );
+        assert_eq!(
+            intv2 / interval!(0.0, 1.0, "()"),
+            Some(Pair::One(interval!(1.0, "(inf"))),
+        );
+        assert_eq!(
+            interval!(-1.0, 2.0, "[]") / interval!(-1.0, 1.0, "[]"),
+            Some(Pair::One(Interval::doubly_unbounded())),
+        );
+        assert_eq!(
+            intv2 / interval!(0.0, 0.0, "[]"),
+            Some(Pair::One(Interval::empty())),
+        );
+        assert_eq!(
+            intv2 / interval!(2.0, "inf"),
+            Some(Pair::One(interval!(0.0, 1.0, "(]"))),
+        );
+        assert_eq!(
+            interval!(1.0, "inf") * interval!("-inf", -1.0, "]"),
+            Some(interval!("-inf", -1.0, "]")),
+        );
+    }
+
+    #[test]
+    fn test_split_at() {
+        let intv1 = interval!(1, 10, "()");
+        assert_eq!(
+            intv1.split_at(&4, SplitSide::Left),
+            (interval!(1, 4, "(]"), interval!(4, 10, "()")),
+        );
+        assert_eq!(
+            intv1.split_at(&4, SplitSide::Right),
+            (interval!(1, 4, "()"), interval!(4, 10, "[)")),
+        );
+        assert_eq!(
+            intv1.split_at(&1, SplitSide::Left),
+            (Interval::empty(), intv1),
+        );
+        assert_eq!(
+            intv1.split_at(&10, SplitSide::Right),
+            (intv1, Interval::empty()),
+        );
+        assert_eq!(
+            intv1.split_at(&0, SplitSide::Right),
+            (Interval::empty(), intv1),
+        );
+        assert_eq!(
+            Interval::<u8>::doubly_unbounded().split_at(&5, SplitSide::Left),
+            (interval!("-inf", 5, "]"), interval!(5, "(inf")),
+        );
+        assert_eq!(
+            Interval::<u8>::empty().split_at(&5, SplitSide::Left),
+            (Interval::empty(), Interval::empty()),
+        );
+
+        let intv2 = interval!(1.0, 2.0, "[]");
+        let (left, right) = intv2.split_at(&1.5, SplitSide::Left);
+        assert_eq!(left, interval!(1.0, 1.5, "[]"));
+        assert_eq!(right, interval!(1.5, 2.0, "(]"));
+    }
+
+    #[test]
+    fn test_translate() {
+        let intv1 = interval!(1, 5, "(]");
+        assert_eq!(intv1.shift(&10), Some(interval!(11, 15, "(]")));
+        assert_eq!(intv1.shift(&-3), Some(interval!(-2, 2, "(]")));
+        assert_eq!(intv1.expand(&1), Some(interval!(0, 6, "(]")));
+        assert_eq!(intv1.shrink(&1), Some(interval!(2, 4, "(]")));
+        assert_eq!(intv1.shrink(&2), Some(interval!(3, 3, "(]")));
+        assert_eq!(intv1.shrink(&2), Some(Interval::empty()));
+        assert_eq!(intv1.shrink(&-1), intv1.expand(&1));
+        assert_eq!(intv1.expand(&-2), Some(Interval::empty()));
+
+        let intv2 = interval!("-inf", 5_u8, "]");
+        assert_eq!(intv2.shift(&2), Some(interval!("-inf", 7, "]")));
+        assert_eq!(intv2.expand(&2), Some(interval!("-inf", 7, "]")));
+        assert_eq!(intv2.shift(&251), None);
+        assert_eq!(interval!(0_u8, 5).expand(&1), None);
+        assert_eq!(
+            interval!(250_u8, 255, "[]").shrink(&10),
+            Some(Interval::empty()),
+        );
+        assert_eq!(
+            interval!(0_u8, 5, "[]").shrink(&10),
+            Some(Interval::empty()),
+        );
+        assert_eq!(interval!(250_u8, "inf").shrink(&10), None);
+        assert_eq!(interval!(-125_i8, 0).shrink(&-10), None);
+        assert_eq!(
+            Interval::<u8>::doubly_unbounded().shift(&200),
+            Some(Interval::doubly_unbounded()),
+        );
+        assert_eq!(
+            Interval::<u8>::empty().shift(&255),
+            Some(Interval::empty())
+        );
+        assert_eq!(Interval::<u8>::empty().expand(&1), Some(Interval::empty()));
+        assert_eq!(Interval::<u8>::empty().shrink(&1), Some(Interval::empty()));
+
+        let intv3 = interval!(1.0, 2.0, "[)");
+        assert_eq!(intv3.shift(&0.5), Some(interval!(1.5, 2.5, "[)")));
+        assert_eq!(intv3.shift(&f64::NAN), None);
+        assert_eq!(interval!(1e308, 1.5e308, "[]").shift(&1e308), None);
+        assert_eq!(interval!(1e308, 1.5e308, "[]").expand(&1e308), None);
+        assert_eq!(
+            interval!(1.0, f64::INFINITY).shift(&1.0),
+            Some(interval!(2.0, f64::INFINITY)),
+        );
+
+        // Scaling
+        let intv4 = interval!(2, 4, "[)");
+        assert_eq!(intv4.scale_around(&0, &3), Some(interval!(6, 12, "[)")));
+        assert_eq!(intv4.scale_around(&3, &2), Some(interval!(1, 5, "[)")));
+        assert_eq!(
+            interval!(2_u8, 4).scale_around(&3, &2),
+            Some(interval!(1, 5)),
+        );
+        assert_eq!(interval!(2_u8, 4).scale_around(&3, &4), None);
+        assert_eq!(intv4.scale_around(&0, &-1), Some(interval!(-4, -2, "(]")));
+        assert_eq!(intv4.scale_around(&3, &0), Some(interval!(3, 3, "[]")));
+        assert_eq!(
+            interval!(2, "inf").scale_around(&0, &-2),
+            Some(interval!("-inf", -4, "]")),
+        );
+        assert_eq!(
+            Interval::<i8>::empty().scale_around(&0, &2),
+            Some(Interval::empty()),
+        );
+        assert_eq!(interval!(1.0, 2.0).scale_around(&0.0, &f64::NAN), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_relation() {
+        let intv1 = interval!(3, 6);
+        let check = |other: Interval<i32>, expected: AllenRelation| {
+            assert_eq!(intv1.relation(other), Some(expected), "{}", other);
+            assert_eq!(
+                other.relation(intv1),
+                Some(expected.inverse()),
+                "{}",
+                other,
+            );
+        };
+        check(interval!(8, 10), AllenRelation::Before);
+        check(interval!(6, 10), AllenRelation::Meets);
+        check(interval!(5, 10, "(]"), AllenRelation::Meets);
+        check(interval!(5, 10, "[]"), AllenRelation::Overlaps);
+        check(interval!(3, 10), AllenRelation::Starts);
+        check(interval!(2, 10, "(]"), AllenRelation::Starts);
+        check(interval!(1, 10), AllenRelation::During);
+        check(interval!("-inf", 6), AllenRelation::Finishes);
+        check(interval!(3, 5, "[]"), AllenRelation::Equals);
+        check(interval!(4, 6), AllenRelation::FinishedBy);
+        check(interval!(4, 5), AllenRelation::Contains);
+        check(interval!(3, 4, "[]"), AllenRelation::StartedBy);
+        check(interval!(1, 4), AllenRelation::OverlappedBy);
+        check(interval!("-inf", 3), AllenRelation::MetBy);
+        check(interval!(0, 1, "[]"), AllenRelation::After);
+        assert_eq!(intv1.relation(Interval::empty()), None);
+        assert_eq!(Interval::empty().relation(intv1), None);
+
+        // Open and closed bounds with floats
+        let intv2 = interval!(1.0, 3.0, "[]");
+        assert_eq!(
+            intv2.relation(interval!(3.0, 5.0, "(]")),
+            Some(AllenRelation::Meets),
+        );
+        assert_eq!(
+            intv2.relation(interval!(3.0, 5.0, "[]")),
+            Some(AllenRelation::Overlaps),
+        );
+        assert_eq!(
+            intv2.relation(interval!(3.0, 3.0, "[]")),
+            Some(AllenRelation::FinishedBy),
+        );
+        assert_eq!(
+            interval!(1.0, 3.0, "()").relation(interval!(3.0, 5.0, "()")),
+            Some(AllenRelation::Before),
+        );
+
+        // The relations are consistent with the composition table
+        let mut intvs = vec![];
+        for lo in 0..5 {
+            for up in lo..5 {
+                intvs.push(interval!(lo, up, "[]"));
+                intvs.push(interval!(lo, up, "()"));
+                intvs.push(interval!(lo, up, "[)"));
+            }
+            intvs.push(interval!(lo, "inf"));
+            intvs.push(interval!("-inf", lo));
+        }
+        intvs.retain(|i| !i.is_empty());
+        let mut seen = AllenRelations::empty();
+        for a in &intvs {
+            for b in &intvs {
+                let ab = a.relation(b).unwrap_or(AllenRelation::Equals);
+                let ba = b.relation(a).unwrap_or(AllenRelation::Equals);
+                assert_eq!(ab.inverse(), ba, "{} {}", a, b);
+                seen.insert(ab);
+                for c in &intvs {
+                    let bc = b.relation(c).unwrap_or(AllenRelation::Equals);
+                    let ac = a.relation(c).unwrap_or(AllenRelation::Equals);
+                    assert!(
+                        ab.compose(bc).contains(ac),
+                        "{} {:?} {} {:?} {} but {:?}",
+                        a,
+                        ab,
+                        b,
+                        bc,
+                        c,
+                        ac,
+                    );
+                }
+            }
+        }
+        assert_eq!(seen, AllenRelations::all());
+
+        // Set operations
+        let before = AllenRelations::from(AllenRelation::Before);
+        assert_eq!(before.len(), 1);
+        assert_eq!(
+            before.inverse(),
+            AllenRelations::from(AllenRelation::After)
+        );
+        assert_eq!(
+            before.compose(AllenRelation::After.into()),
+            AllenRelations::all(),
+        );
+        assert!(before.intersection(AllenRelation::After.into()).is_empty());
+        assert_eq!(AllenRelations::all().len(), 13);
+        assert_eq!(AllenRelations::all().inverse(), AllenRelations::all());
+    }
+
     #[test]
     fn test_macro() {
         let intv1 = interval!(1, 2);
@@ -1224,6 +2522,7 @@ pub(crate) mod test {
 mod multi {
     use crate::multi::Policy;
     use crate::*;
+    use ::core::convert::TryFrom;
 
     fn insert_via_trait<T, E, I>(into: &mut E, from: I)
     where
@@ -1557,41 +2856,430 @@ mod multi {
     }
 
     #[test]
-    fn test_equals() {
-        let m1 = IntervalSet::new_joining([
-            interval!(3, 10, "[]"),
-            interval!(15, 20, "()"),
-        ]);
-        let m2 = IntervalSet::new([
-            interval!(2, 5, "()"),
-            interval!(5, 11, "[)"),
-            interval!(16, 19, "[]"),
-        ]);
-        assert_eq!(m1, m2);
-        assert_eq!(m2, m1);
-        assert!(m1.equivalent(&m2));
-        assert!(m1.equivalent(m2));
+    fn test_splitting() {
+        let mut m = IntervalSet::empty_splitting();
+        m.extend([interval!(1, 3), interval!(2, 4), interval!(4, 5)]);
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![
+                &interval!(1, 2),
+                &interval!(2, 3),
+                &interval!(3, 4),
+                &interval!(4, 5),
+            ],
+        );
 
-        let m4 =
-            IntervalSet::new([interval!(2, 5, "()"), interval!(5, 11, "[)")]);
-        assert_ne!(m1, m4); // same length, different intervals
-        assert_ne!(m4, m1); // same length, different intervals
+        // Same, but inserting one interval at a time
+        let mut m2 = IntervalSet::new_single_splitting(1);
+        m2.add(interval!(1, 3));
+        m2.add(interval!(2, 4));
+        m2.add(interval!(4, 5));
+        assert_eq!(m, m2);
 
-        let m5 = IntervalSet::new([interval!(2, 5, "()")]);
-        assert_ne!(m1, m5); // different lengths
-        assert_ne!(m5, m1); // different lengths
+        // Adding an existing border has no effect
+        m.add(interval!(2, 4));
+        assert_eq!(m, m2);
+        assert_eq!(m.len(), 4);
 
-        let m6 = IntervalSet::new_joining([
-            interval!(3, 10, "[]"),
-            interval!(15, 20, "()"),
-            interval!(25, 30, "()"),
-        ]);
-        assert_ne!(m1, m6); // same initial intervals, but have more
-        assert_ne!(m6, m1); // same initial intervals, but have more
+        // Inserting at the end, or with gaps
+        m.add(interval!(8, 10, "[]"));
+        m.add(interval!(6, 9));
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![
+                &interval!(1, 2),
+                &interval!(2, 3),
+                &interval!(3, 4),
+                &interval!(4, 5),
+                &interval!(6, 8),
+                &interval!(8, 9),
+                &interval!(9, 10, "[]"),
+            ],
+        );
 
-        let intv1 = interval!(3, 20, "[)");
-        let pairs = intv1 - interval!(10, 15, "(]");
-        let m3 = IntervalSet::from_pair(pairs);
+        // An interval covering everything preserves all borders
+        m.add(interval!(0, 20));
+        assert_eq!(m.len(), 10);
+        assert_eq!(m.iter().next(), Some(&interval!(0, 1)));
+        assert_eq!(m.iter().last(), Some(&interval!(10, 20, "()")));
+        assert!(m.contains_interval(interval!(0, 20)));
+
+        m.clear();
+        m.extend([interval!(1, 3), interval!(2, 4), interval!(6, 8)]);
+        assert!(m.contains(1));
+        assert!(m.contains(2));
+        assert!(m.contains(3));
+        assert!(!m.contains(4));
+        assert!(!m.contains(5));
+        assert!(m.contains(6));
+        assert!(m.contains_interval(interval!(1, 4)));
+        assert!(!m.contains_interval(interval!(1, 5)));
+        assert_eq!(m.convex_hull(), interval!(1, 8));
+
+        // Removing preserves the remaining borders
+        m.remove_interval(interval!(2, 3));
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![&interval!(1, 2), &interval!(3, 4), &interval!(6, 8)],
+        );
+
+        // Intersection with a set using a different policy
+        m.clear();
+        m.extend([interval!(1, 3), interval!(2, 4)]);
+        assert_eq!(
+            m.intersection_set(IntervalSet::new_joining([interval!(0, 10)])),
+            m,
+        );
+        assert_eq!(
+            IntervalSet::new_joining([interval!(0, 10)]).intersection_set(&m),
+            IntervalSet::new_joining([interval!(1, 4)]),
+        );
+        assert_eq!(
+            m.intersection_interval(interval!(2, 10)),
+            IntervalSet::new_splitting([interval!(2, 3), interval!(3, 4)]),
+        );
+    }
+
+    #[test]
+    fn test_canonical() {
+        let mut m = IntervalSet::empty_canonical();
+        m.extend([interval!(1, 3, "[]"), interval!(5, 8, "()")]);
+        assert_eq!(format!("{}", m), "{[1, 4), [6, 8)}");
+        m.add(interval!(3, 5, "(]"));
+        assert_eq!(format!("{}", m), "{[1, 8)}");
+
+        let m2 = IntervalSet::new_canonical([interval!(0, 7, "(]")]);
+        assert_eq!(format!("{}", m2), "{[1, 8)}");
+        assert_eq!(m, m2);
+
+        let single = IntervalSet::new_single_canonical(4_u8);
+        assert_eq!(format!("{}", single), "{[4, 5)}");
+        let single = IntervalSet::new_single_canonical(u8::MAX);
+        assert_eq!(format!("{}", single), "{[255, 255]}");
+
+        // Wrapping other policies
+        let mut m3 = IntervalSet::<u32, Canonical<Separating>>::empty();
+        m3.extend([interval!(1, 3, "[]"), interval!(4, 6, "[]")]);
+        assert_eq!(format!("{}", m3), "{[1, 4), [4, 7)}");
+
+        let mut m4 = IntervalSet::<u32, Canonical<Splitting>>::empty();
+        m4.extend([interval!(1, 5, "[]"), interval!(2, 3, "[]")]);
+        assert_eq!(format!("{}", m4), "{[1, 2), [2, 4), [4, 6)}");
+
+        // All operations preserve the canonical form
+        let set = IntervalSet::new_canonical([interval!(1, 10)]);
+        let intv1 = interval!(3, 5, "[]");
+        let other = IntervalSet::new_joining([intv1]);
+        let check = |s: IntervalSet<i32, Canonical>, expected: &str| {
+            assert_eq!(format!("{}", s), expected);
+        };
+        let mut removed = set.clone();
+        removed.remove(5);
+        check(removed, "{[1, 5), [6, 10)}");
+        let mut removed = set.clone();
+        removed.remove_interval(intv1);
+        check(removed, "{[1, 3), [6, 10)}");
+        check(set.difference(5), "{[1, 5), [6, 10)}");
+        check(set.difference_interval(intv1), "{[1, 3), [6, 10)}");
+        check(&set - intv1, "{[1, 3), [6, 10)}");
+        check(set.difference_set(&other), "{[1, 3), [6, 10)}");
+        check(set.intersection_interval(intv1), "{[3, 6)}");
+        check(&set & intv1, "{[3, 6)}");
+        check(set.intersection_set(&other), "{[3, 6)}");
+        check(
+            set.union_interval(interval!(12, 15, "[]")),
+            "{[1, 10), [12, 16)}",
+        );
+        check(set.union_set(&other), "{[1, 10)}");
+        check(
+            set.symmetric_difference_interval(interval!(5, 12, "[]")),
+            "{[1, 5), [10, 13)}",
+        );
+        check(set.symmetric_difference_set(&other), "{[1, 3), [6, 10)}");
+        check(set.complement(), "{(, 1), [10,)}");
+        check(!&set - interval!(10, 10, "[]"), "{(, 1), [11,)}");
+        check(
+            set.complement_within(interval!(0, 20, "[]")),
+            "{[0, 1), [10, 21)}",
+        );
+        check(IntervalSet::new_single(4), "{[4, 5)}");
+        check(
+            IntervalSet::from_pair(interval!(1, 10).difference(intv1)),
+            "{[1, 3), [6, 10)}",
+        );
+        check(set.map(|v| v * 2), "{[2, 20)}");
+        check(set.map_decreasing(|v| -v), "{[-9, 0)}");
+        check(set.shift(&2).unwrap_or_default(), "{[3, 12)}");
+
+        let mut btree = BTreeIntervalSet::<i32, Canonical>::empty();
+        btree.add(interval!(1, 10));
+        btree.remove(5);
+        assert_eq!(
+            btree.iter().collect::<Vec<_>>(),
+            vec![&interval!(1, 5), &interval!(6, 10)],
+        );
+        assert_eq!(
+            btree
+                .intersection_interval(interval!(3, 7, "[]"))
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![&interval!(3, 5), &interval!(6, 8)],
+        );
+    }
+
+    #[test]
+    fn test_map() {
+        let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+        assert_eq!(
+            set.map(|v| v * 10),
+            IntervalSet::new_joining([interval!(10, 30), interval!(50, 80)]),
+        );
+        assert_eq!(set.map(|v| v * 10).len(), 2);
+
+        // Intervals that become empty are dropped
+        assert_eq!(
+            set.map(|v| *v.min(&4)),
+            IntervalSet::new_joining([interval!(1, 3)]),
+        );
+        assert!(IntervalSet::<u32>::empty().map(|v| v * 2).is_empty());
+
+        let decr = set.map_decreasing(|v| -v);
+        assert_eq!(
+            decr.iter().collect::<Vec<_>>(),
+            vec![&interval!(-8, -5, "(]"), &interval!(-3, -1, "(]")],
+        );
+
+        // Intervals that overlap after mapping are combined by the policy
+        let set2 = IntervalSet::new_separating([
+            interval!(1, 3, "[]"),
+            interval!(4, 6, "[]"),
+        ]);
+        let mapped = set2.map(|v| v / 2);
+        assert_eq!(
+            mapped.iter().collect::<Vec<_>>(),
+            vec![&interval!(0, 1, "[]"), &interval!(2, 3, "[]")],
+        );
+        let mapped = set2.map(|v| v / 3);
+        assert_eq!(
+            mapped.iter().collect::<Vec<_>>(),
+            vec![&interval!(0, 2, "[]")],
+        );
+        let mapped = set2.map_decreasing(|v| 10 - v);
+        assert_eq!(
+            mapped.iter().collect::<Vec<_>>(),
+            vec![&interval!(4, 6, "[]"), &interval!(7, 9, "[]")],
+        );
+    }
+
+    #[test]
+    fn test_translate() {
+        let set = IntervalSet::new_joining([interval!(1, 3), interval!(5, 8)]);
+        assert_eq!(
+            set.shift(&10),
+            Some(IntervalSet::new_joining([
+                interval!(11, 13),
+                interval!(15, 18)
+            ])),
+        );
+        assert_eq!(set.expand(&1).map(|s| s.len()), Some(1));
+        assert_eq!(
+            set.shrink(&1),
+            Some(IntervalSet::new_joining([interval!(6, 7)])),
+        );
+        assert_eq!(
+            set.scale_around(&0, &2),
+            Some(IntervalSet::new_joining([
+                interval!(2, 6),
+                interval!(10, 16)
+            ])),
+        );
+        assert_eq!(
+            set.scale_around(&0, &-1),
+            Some(IntervalSet::new_joining([
+                interval!(-8, -5, "(]"),
+                interval!(-3, -1, "(]"),
+            ])),
+        );
+        assert_eq!(set.shift(&i32::MAX), None);
+
+        // With a separating policy, intervals that start overlapping are
+        // merged, but not those that only touch
+        let set2 = IntervalSet::new_separating([
+            interval!(1, 3),
+            interval!(4, 8),
+            interval!(10, 12),
+        ]);
+        assert_eq!(
+            set2.expand(&1)
+                .map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(0, 9), interval!(9, 13)]),
+        );
+    }
+
+    #[test]
+    fn test_split_at_many() {
+        let intv1 = interval!(0, 10, "(]");
+        let split = intv1.split_at_many([5, 2, 20, 5]);
+        assert_eq!(
+            split.iter().collect::<Vec<_>>(),
+            vec![
+                &interval!(0, 2, "()"),
+                &interval!(2, 5),
+                &interval!(5, 10, "[]")
+            ],
+        );
+        assert_eq!(
+            intv1.split_at_many([]).iter().collect::<Vec<_>>(),
+            vec![&intv1],
+        );
+        assert!(Interval::<u8>::empty().split_at_many([1, 2]).is_empty());
+
+        let split = Interval::<f32>::doubly_unbounded().split_at_many([0.0]);
+        assert_eq!(
+            split.iter().collect::<Vec<_>>(),
+            vec![&interval!("-inf", 0.0, ")"), &interval!(0.0, "[inf")],
+        );
+
+        // Points that cannot be compared are ignored
+        let split = interval!(0.0, 10.0).split_at_many([5.0, f64::NAN, 2.0]);
+        assert_eq!(
+            split.iter().collect::<Vec<_>>(),
+            vec![
+                &interval!(0.0, 2.0),
+                &interval!(2.0, 5.0),
+                &interval!(5.0, 10.0)
+            ],
+        );
+    }
+
+    #[test]
+    fn test_partition() {
+        let split = interval!(0, 10).partition(3);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(0, 4), interval!(4, 7), interval!(7, 10)]),
+        );
+        let split = interval!(0, 10, "(]").partition(2);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(0, 6, "()"), interval!(6, 10, "[]")]),
+        );
+        let split = interval!(1, 3, "[]").partition(5);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                interval!(1, 2),
+                interval!(2, 3),
+                interval!(3, 3, "[]")
+            ]),
+        );
+        let split = interval!(0_u8, 255, "[]").partition(2);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(0, 128), interval!(128, 255, "[]")]),
+        );
+        assert_eq!(interval!(0, 10).partition(1).map(|s| s.len()), Some(1));
+        assert_eq!(
+            Interval::<u8>::empty().partition(3).map(|s| s.len()),
+            Some(0),
+        );
+        assert!(interval!(0, 10).partition(0).is_none());
+        assert!(interval!(0, "inf").partition(2).is_none());
+        assert!(interval!("-inf", 0).partition(2).is_none());
+
+        // The number of values does not fit in usize
+        let split = interval!(0_u64, u64::MAX, "[]").partition(4);
+        let quarter = 1_u64 << 62;
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                interval!(0, quarter),
+                interval!(quarter, 2 * quarter),
+                interval!(2 * quarter, 3 * quarter),
+                interval!(3 * quarter, u64::MAX, "[]"),
+            ]),
+        );
+        let split = interval!(i128::MIN, i128::MAX).partition(2);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(i128::MIN, 0), interval!(0, i128::MAX)]),
+        );
+        let split = interval!(-128_i8, 127, "[]").partition(2);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![interval!(-128, 0), interval!(0, 127, "[]")]),
+        );
+    }
+
+    #[test]
+    fn test_partition_by_length() {
+        let split = interval!(0.0, 3.0, "(]").partition_by_length(3);
+        assert_eq!(
+            split.map(|s| s.iter().cloned().collect::<Vec<_>>()),
+            Some(vec![
+                interval!(0.0, 1.0, "()"),
+                interval!(1.0, 2.0),
+                interval!(2.0, 3.0, "[]"),
+            ]),
+        );
+        assert_eq!(
+            interval!(0.0_f32, 1.0)
+                .partition_by_length(1)
+                .map(|s| s.len()),
+            Some(1),
+        );
+        assert_eq!(
+            Interval::<f64>::empty()
+                .partition_by_length(3)
+                .map(|s| s.len()),
+            Some(0),
+        );
+        assert!(interval!(0.0, 1.0).partition_by_length(0).is_none());
+        assert!(interval!(0.0, "inf").partition_by_length(2).is_none());
+        assert!(interval!(-f64::MAX, f64::MAX)
+            .partition_by_length(2)
+            .is_none());
+
+        // Integers cannot divide their lengths, use partition() instead
+        assert!(interval!(0_u32, 10).partition_by_length(2).is_none());
+    }
+
+    #[test]
+    fn test_equals() {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+        ]);
+        let m2 = IntervalSet::new([
+            interval!(2, 5, "()"),
+            interval!(5, 11, "[)"),
+            interval!(16, 19, "[]"),
+        ]);
+        assert_eq!(m1, m2);
+        assert_eq!(m2, m1);
+        assert!(m1.equivalent(&m2));
+        assert!(m1.equivalent(m2));
+
+        let m4 =
+            IntervalSet::new([interval!(2, 5, "()"), interval!(5, 11, "[)")]);
+        assert_ne!(m1, m4); // same length, different intervals
+        assert_ne!(m4, m1); // same length, different intervals
+
+        let m5 = IntervalSet::new([interval!(2, 5, "()")]);
+        assert_ne!(m1, m5); // different lengths
+        assert_ne!(m5, m1); // different lengths
+
+        let m6 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 30, "()"),
+        ]);
+        assert_ne!(m1, m6); // same initial intervals, but have more
+        assert_ne!(m6, m1); // same initial intervals, but have more
+
+        let intv1 = interval!(3, 20, "[)");
+        let pairs = intv1 - interval!(10, 15, "(]");
+        let m3 = IntervalSet::from_pair(pairs);
         assert_eq!(m1, m3);
 
         let intv1 = interval!(3, 20, "[)");
@@ -1659,15 +3347,1446 @@ mod multi {
     }
 
     #[test]
-    fn test_display() {
-        let empty = IntervalSet::<f32>::empty_joining();
-        assert_eq!(format!("{}", empty), "empty");
+    fn test_set_algebra() {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        let m2 = IntervalSet::new_separating([
+            interval!(1, 4),
+            interval!(8, 16),
+            interval!(16, 27),
+            interval!(38, 50),
+        ]);
+        let empty = IntervalSet::empty_joining();
+
+        assert_eq!(
+            m1.union_set(&m2),
+            IntervalSet::new_joining([interval!(1, 50)]),
+        );
+        assert_eq!(
+            m2.union_set(&m1),
+            IntervalSet::new_separating([interval!(1, 16), interval!(16, 50)]),
+        );
+        assert_eq!(m1.union_set(&empty), m1);
+        assert_eq!(empty.union_set(&m1), m1);
+        assert_eq!(
+            m1.union_interval(interval!(10, 15, "[]")),
+            IntervalSet::new_joining([
+                interval!(3, 20, "[)"),
+                interval!(25, 40, "[)"),
+            ]),
+        );
+
+        assert_eq!(
+            m1.difference_set(&m2),
+            IntervalSet::new_joining([
+                interval!(4, 8, "[)"),
+                interval!(27, 38, "[)"),
+            ]),
+        );
+        assert_eq!(
+            m2.difference_set(&m1),
+            IntervalSet::new_separating([
+                interval!(1, 3),
+                interval!(10, 15, "(]"),
+                interval!(20, 25, "[)"),
+                interval!(40, 50),
+            ]),
+        );
+        assert_eq!(m1.difference_set(&empty), m1);
+        assert_eq!(empty.difference_set(&m1), empty);
+        assert_eq!(m1.difference_set(&m1), empty);
+
+        assert_eq!(
+            m1.symmetric_difference_set(&m2),
+            IntervalSet::new_joining([
+                interval!(1, 3),
+                interval!(4, 8, "[)"),
+                interval!(10, 15, "(]"),
+                interval!(20, 25, "[)"),
+                interval!(27, 38, "[)"),
+                interval!(40, 50),
+            ]),
+        );
+        assert_eq!(m1.symmetric_difference_set(&m1), empty);
+        assert_eq!(m1.symmetric_difference_set(&empty), m1);
+        assert_eq!(
+            m1.symmetric_difference_interval(interval!(5, 30)),
+            IntervalSet::new_joining([
+                interval!(3, 5, "[)"),
+                interval!(10, 15, "(]"),
+                interval!(20, 25, "[)"),
+                interval!(30, 40, "[)"),
+            ]),
+        );
+
+        // Operators
+        assert_eq!(&m1 | &m2, m1.union_set(&m2));
+        assert_eq!(&m1 & &m2, m1.intersection_set(&m2));
+        assert_eq!(&m1 - &m2, m1.difference_set(&m2));
+        assert_eq!(&m1 ^ &m2, m1.symmetric_difference_set(&m2));
+        assert_eq!(&m1 | interval!(1, 2), m1.union_interval(interval!(1, 2)));
+        assert_eq!(
+            m1.clone() & interval!(4, 16),
+            m1.intersection_interval(interval!(4, 16)),
+        );
+        assert_eq!(
+            m1.clone() - &interval!(4, 16),
+            m1.difference_interval(interval!(4, 16)),
+        );
+
+        let mut m3 = m1.clone();
+        m3 |= &m2;
+        assert_eq!(m3, &m1 | &m2);
+        m3 &= interval!(0, 10);
+        assert_eq!(m3, IntervalSet::new_joining([interval!(1, 10)]));
+        m3 ^= interval!(5, 15);
+        assert_eq!(
+            m3,
+            IntervalSet::new_joining([interval!(1, 5), interval!(10, 15)]),
+        );
+        m3 -= m2.clone();
+        assert_eq!(m3, IntervalSet::new_joining([interval!(4, 5)]));
+
+        // By-value operators and assignments match the methods
+        assert_eq!(m1.clone() | &m2, m1.union_set(&m2));
+        assert_eq!(m1.clone() & m2.clone(), m1.intersection_set(&m2));
+        assert_eq!(m1.clone() - &m2, m1.difference_set(&m2));
+        assert_eq!(m1.clone() ^ &m2, m1.symmetric_difference_set(&m2));
+        assert_eq!(
+            m1.clone() ^ interval!(5, 30),
+            m1.symmetric_difference_interval(interval!(5, 30)),
+        );
+        let mut m4 = m1.clone();
+        m4 &= &m2;
+        assert_eq!(m4, m1.intersection_set(&m2));
+        let mut m4 = m1.clone();
+        m4 ^= &m2;
+        assert_eq!(m4, m1.symmetric_difference_set(&m2));
+        let mut m4 = m1.clone();
+        m4 -= interval!(4, 16);
+        assert_eq!(m4, m1.difference_interval(interval!(4, 16)));
+
+        // Assignments with a different policy on the right side
+        let sep = IntervalSet::new_separating([
+            interval!(2, 4, "[)"),
+            interval!(4, 6, "[)"),
+        ]);
+        let mut m4 = m1.clone();
+        m4 |= &sep;
+        assert_eq!(m4, m1.union_set(&sep));
+        let mut m4 = m1.clone();
+        m4 -= &sep;
+        assert_eq!(m4, m1.difference_set(&sep));
+    }
+
+    #[test]
+    fn test_complement() {
+        let m1 = IntervalSet::new_joining([
+            interval!(3_u8, 10, "[]"),
+            interval!(15, 20, "()"),
+        ]);
+        assert_eq!(
+            m1.complement(),
+            IntervalSet::new_joining([
+                interval!("-inf", 3, ")"),
+                interval!(10, 15, "(]"),
+                interval!(20, "[inf"),
+            ]),
+        );
+        assert_eq!(!&m1, m1.complement());
+        assert_eq!(!!m1.clone(), m1);
+        assert_eq!(
+            IntervalSet::<u8>::empty().complement(),
+            IntervalSet::new_joining([Interval::doubly_unbounded()]),
+        );
+        assert_eq!(
+            IntervalSet::new_joining([interval!(0_u8, 255, "[]")]).complement(),
+            IntervalSet::empty(),
+        );
 
+        // Touching intervals do not result in empty gaps
+        let m2 =
+            IntervalSet::new_separating([interval!(1_u8, 3), interval!(3, 5)]);
+        assert_eq!(
+            m2.complement(),
+            IntervalSet::new_separating([
+                interval!("-inf", 1, ")"),
+                interval!(5, "[inf"),
+            ]),
+        );
+
+        assert_eq!(
+            m1.complement_within(interval!(5, 30)),
+            IntervalSet::new_joining([
+                interval!(10, 15, "(]"),
+                interval!(20, 30, "[)"),
+            ]),
+        );
+        assert_eq!(m1.complement_within(interval!(4, 8)), IntervalSet::empty());
+    }
+
+    #[test]
+    fn test_find() {
         let m1 = IntervalSet::new_joining([
             interval!(3, 10, "[]"),
             interval!(15, 20, "()"),
             interval!(25, 40, "[)"),
         ]);
-        assert_eq!(format!("{}", m1), "{[3, 10], (15, 20), [25, 40)}");
+        assert_eq!(m1.find(2), None);
+        assert_eq!(m1.find(3), Some(&interval!(3, 10, "[]")));
+        assert_eq!(m1.find(10), Some(&interval!(3, 10, "[]")));
+        assert_eq!(m1.find(15), None);
+        assert_eq!(m1.find(16), Some(&interval!(15, 20, "()")));
+        assert_eq!(m1.find(39), Some(&interval!(25, 40, "[)")));
+        assert_eq!(m1.find(40), None);
+        assert_eq!(IntervalSet::<u32>::empty().find(1), None);
+
+        assert_eq!(
+            m1.overlapping(interval!(10, 17)),
+            [interval!(3, 10, "[]"), interval!(15, 20, "()")],
+        );
+        assert_eq!(
+            m1.overlapping(interval!(11, 15, "[]")),
+            [] as [Interval<u32>; 0],
+        );
+        assert_eq!(m1.overlapping(interval!(0, 100)).len(), 3);
+        assert_eq!(m1.overlapping(interval!(40, 100)).len(), 0);
+        assert_eq!(m1.overlapping(Interval::empty()).len(), 0);
+        assert!(m1.intersects_interval(interval!(39, 50)));
+        assert!(!m1.intersects_interval(interval!(40, 50)));
+
+        // Removing only affects the overlapping intervals
+        let mut m2 = m1.clone();
+        m2.remove_interval(interval!(5, 30));
+        assert_eq!(
+            m2,
+            IntervalSet::new_joining([
+                interval!(3, 5, "[)"),
+                interval!(30, 40, "[)"),
+            ]),
+        );
+        m2.remove_interval(interval!(35, 36));
+        assert_eq!(m2.len(), 3);
+        m2.remove_interval(Interval::empty());
+        assert_eq!(m2.len(), 3);
+    }
+
+    #[test]
+    fn test_total_length() {
+        let intvs = [interval!(1, 3, "[]"), interval!(4, 8), interval!(10, 12)];
+        assert_eq!(IntervalSet::new_joining(intvs).total_length(), Some(9_u32));
+        assert_eq!(
+            IntervalSet::new_separating(intvs).total_length(),
+            Some(9_u32),
+        );
+        assert_eq!(IntervalSet::<i32>::empty().total_length(), Some(0));
+        assert_eq!(
+            IntervalSet::new_joining([interval!(1, 3), interval!(5, "inf")])
+                .total_length(),
+            None,
+        );
+
+        // Overflow when adding the lengths
+        let halves = [interval!(0_u8, 127, "[]"), interval!(128_u8, 255, "[)")];
+        assert_eq!(
+            IntervalSet::new_separating(halves).total_length(),
+            Some(255),
+        );
+        let halves = [interval!(0_u8, 127, "[]"), interval!(128_u8, 255, "[]")];
+        assert_eq!(IntervalSet::new_separating(halves).total_length(), None);
+    }
+
+    #[test]
+    fn test_cardinality() {
+        let set = IntervalSet::new_separating([
+            interval!(1, 3, "[]"),
+            interval!(4, 8),
+            interval!(10, 12, "()"),
+        ]);
+        assert_eq!(set.cardinality(), Some(8));
+        assert_eq!(IntervalSet::<u8>::empty().cardinality(), Some(0));
+
+        let halves = IntervalSet::new_separating([
+            interval!("-inf", 0_i128, ")"),
+            interval!(0_i128, "inf"),
+        ]);
+        assert_eq!(halves.len(), 2);
+        assert_eq!(halves.cardinality(), None);
+
+        let set = IntervalSet::new_joining([
+            interval!("-inf", 5_u64, "]"),
+            interval!(10_u64, "inf"),
+        ]);
+        assert_eq!(set.cardinality(), Some(u128::from(u64::MAX) - 3));
+    }
+
+    #[test]
+    fn test_gaps() {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        assert_eq!(
+            m1.gaps().collect::<Vec<_>>(),
+            [interval!(10, 15, "(]"), interval!(20, 25, "[)")],
+        );
+        assert_eq!(IntervalSet::<u32>::empty().gaps().count(), 0);
+        assert_eq!(
+            IntervalSet::new_joining([interval!(1, 2)]).gaps().count(),
+            0
+        );
+
+        // No gap between contiguous intervals
+        let m2 = IntervalSet::new_separating([
+            interval!(1, 3),
+            interval!(3, 5),
+            interval!(8, 10),
+        ]);
+        assert_eq!(m2.gaps().collect::<Vec<_>>(), [interval!(5, 8)]);
+
+        assert_eq!(
+            m1.gaps_within(&interval!(0, 50)).collect::<Vec<_>>(),
+            [
+                interval!(0, 3),
+                interval!(10, 15, "(]"),
+                interval!(20, 25, "[)"),
+                interval!(40, 50),
+            ],
+        );
+        assert_eq!(
+            m1.gaps_within(&interval!(5, 22)).collect::<Vec<_>>(),
+            [interval!(10, 15, "(]"), interval!(20, 22, "[)")],
+        );
+        assert_eq!(m1.gaps_within(&interval!(4, 8)).count(), 0);
+        assert_eq!(
+            m1.gaps_within(&interval!(11, 13)).collect::<Vec<_>>(),
+            [interval!(11, 13)],
+        );
+        assert_eq!(m1.gaps_within(&Interval::empty()).count(), 0);
+        assert_eq!(
+            IntervalSet::<u32>::empty()
+                .gaps_within(&interval!(1, 5))
+                .collect::<Vec<_>>(),
+            [interval!(1, 5)],
+        );
+        assert_eq!(
+            m1.gaps_within(&Interval::doubly_unbounded())
+                .collect::<Vec<_>>(),
+            m1.complement().iter().cloned().collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let empty = IntervalSet::<f32>::empty_joining();
+        assert_eq!(format!("{}", empty), "empty");
+
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        assert_eq!(format!("{}", m1), "{[3, 10], (15, 20), [25, 40)}");
+
+        let m2 = IntervalSet::new_separating([
+            interval!(1, 3),
+            interval!(3, 5),
+            interval!(8, "[inf"),
+        ]);
+        assert_eq!(format!("{}", m2), "{[1, 3), [3, 5), [8,)}");
+    }
+
+    #[test]
+    fn test_fromstr() -> Result<(), ParseError<::core::num::ParseIntError>> {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        assert_eq!(m1.to_string().parse::<IntervalSet<u32>>()?, m1);
+        assert_eq!(IntervalSet::try_from("{[3, 10], (15, 20), [25, 40)}")?, m1);
+        assert_eq!(
+            "{(15,20),[25, 30),  [3,10],[28,40)}"
+                .parse::<IntervalSet<u32>>()?,
+            m1
+        );
+        assert_eq!("empty".parse::<IntervalSet<u32>>()?, IntervalSet::empty());
+        assert_eq!(" {} ".parse::<IntervalSet<u32>>()?, IntervalSet::empty());
+        assert_eq!(
+            "{[1, 3), (5, 7], [9,)}".parse::<IntervalSet<u32>>()?,
+            IntervalSet::new_joining([
+                interval!(1, 3),
+                interval!(5, 7, "(]"),
+                interval!(9, "[inf"),
+            ]),
+        );
+
+        let m2 = IntervalSet::new_splitting([interval!(1, 5), interval!(3, 8)]);
+        assert_eq!(m2.to_string().parse::<IntervalSet<u32, Splitting>>()?, m2);
+        assert_eq!(
+            "{[1, 5), [3, 8)}".parse::<IntervalSet<u32, Splitting>>()?,
+            m2
+        );
+
+        // Errors
+        assert_eq!(
+            "[1, 3)".parse::<IntervalSet<u32>>(),
+            Err(ParseError::InvalidInput),
+        );
+        assert_eq!(
+            "{[1, 3) a}".parse::<IntervalSet<u32>>(),
+            Err(ParseError::InvalidInput),
+        );
+        assert_eq!(
+            "{[1, 3), [4, 5".parse::<IntervalSet<u32>>(),
+            Err(ParseError::InvalidInput),
+        );
+        assert_eq!(
+            "{[1, 3), [4, 5}".parse::<IntervalSet<u32>>(),
+            Err(ParseError::InvalidMember(1)),
+        );
+        assert_eq!(
+            "{[1, 3), [4, 5), [, 8]}".parse::<IntervalSet<u32>>(),
+            Err(ParseError::InvalidMember(2)),
+        );
+        assert!(matches!(
+            "{[1, 3), [a, 5)}".parse::<IntervalSet<u32>>(),
+            Err(ParseError::MemberBound(1, _)),
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_postgres() -> Result<(), ParseError<::core::num::ParseIntError>> {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        assert_eq!(
+            IntervalSet::<u32>::parse_postgres("{ [1,3) , EMPTY,empty, (,2]}")?,
+            IntervalSet::new_joining([interval!("-inf", 3, ")")]),
+        );
+        assert_eq!(
+            IntervalSet::<u32>::parse_postgres(" {} ")?,
+            IntervalSet::empty()
+        );
+        assert_eq!(
+            IntervalSet::<u32>::parse_postgres("{empty}")?,
+            IntervalSet::empty()
+        );
+        assert_eq!(m1.postgres().to_string(), "{[3,10],(15,20),[25,40)}");
+        assert_eq!(
+            IntervalSet::<u32>::parse_postgres(&m1.postgres().to_string())?,
+            m1
+        );
+        assert_eq!(IntervalSet::<u32>::empty().postgres().to_string(), "{}");
+        let m3 = IntervalSet::new_joining([
+            Interval::new_closed_open(' ', '"'),
+            Interval::new_closed_closed(')', ','),
+        ]);
+        assert_eq!(m3.postgres().to_string(), r#"{[" ",""""),[")",","]}"#);
+        assert_eq!(
+            IntervalSet::<char>::parse_postgres(&m3.postgres().to_string()),
+            Ok(m3),
+        );
+
+        // Errors
+        let invalid = |s: &str| {
+            IntervalSet::<u32>::parse_postgres(s)
+                .map_err(|e| (e.offset(), e.into_error()))
+        };
+        assert_eq!(invalid("[1,3)"), Err((0, ParseError::InvalidInput)));
+        assert_eq!(invalid("empty"), Err((0, ParseError::InvalidInput)));
+        assert_eq!(invalid("{[1,3) a}"), Err((7, ParseError::InvalidInput)));
+        assert_eq!(
+            invalid("{[1,3), [4,5"),
+            Err((12, ParseError::InvalidMember(1)))
+        );
+        assert_eq!(
+            invalid("{[1,3), [4,5}"),
+            Err((13, ParseError::InvalidMember(1)))
+        );
+        assert_eq!(
+            invalid("{[1,3), [4,5) [5,8]}"),
+            Err((14, ParseError::InvalidInput))
+        );
+        assert_eq!(invalid("{[1,3),}"), Err((7, ParseError::InvalidInput)));
+        assert_eq!(invalid("{,}"), Err((1, ParseError::InvalidInput)));
+        assert_eq!(invalid("{} {}"), Err((3, ParseError::InvalidInput)));
+        assert!(matches!(
+            invalid("{[1,3), empty, [a,5)}"),
+            Err((16, ParseError::MemberBound(2, _))),
+        ));
+        // A quoted ")" does not terminate the range
+        assert!(matches!(
+            invalid(r#"{["1","3"), ["5","8)"]}"#),
+            Err((17, ParseError::MemberBound(1, _))),
+        ));
+        // Whitespace within the brackets is part of the bounds
+        assert!(matches!(
+            invalid("{[1,3), [4, 5)}"),
+            Err((11, ParseError::MemberBound(1, _))),
+        ));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod map {
+    use crate::*;
+
+    #[test]
+    fn test_insert() {
+        let mut m = IntervalMap::empty();
+        assert!(m.is_empty());
+        m.insert(interval!(1, 3), 'a');
+        m.insert(interval!(2, 4), 'b');
+        m.insert(interval!(4, 5), 'b');
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![(&interval!(1, 2), &'a'), (&interval!(2, 5), &'b')],
+        );
+
+        // Inserting in the middle splits an existing interval (and nothing
+        // is left after 4 for integers)
+        m.insert(interval!(3, 4, "[]"), 'c');
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![
+                (&interval!(1, 2), &'a'),
+                (&interval!(2, 3), &'b'),
+                (&interval!(3, 4, "[]"), &'c'),
+            ],
+        );
+
+        // Overriding with the same value joins intervals on both sides
+        m.insert(interval!(3, 5), 'b');
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![(&interval!(1, 2), &'a'), (&interval!(2, 5), &'b')],
+        );
+
+        // Touching intervals with different values are not joined
+        m.insert(interval!(5, 8), 'c');
+        m.insert(interval!(10, "inf"), 'c');
+        assert_eq!(m.len(), 4);
+
+        m.insert(Interval::empty(), 'd');
+        assert_eq!(m.len(), 4);
+
+        m.insert(Interval::doubly_unbounded(), 'd');
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![(&Interval::doubly_unbounded(), &'d')],
+        );
+
+        m.clear();
+        assert!(m.is_empty());
+    }
+
+    #[test]
+    fn test_get() {
+        let m = IntervalMap::new([
+            (interval!(1, 3), 'a'),
+            (interval!(5, 8, "(]"), 'b'),
+            (interval!(10, "inf"), 'c'),
+        ]);
+        assert_eq!(m.get(0), None);
+        assert_eq!(m.get(1), Some(&'a'));
+        assert_eq!(m.get(3), None);
+        assert_eq!(m.get(5), None);
+        assert_eq!(m.get(6), Some(&'b'));
+        assert_eq!(m.get(8), Some(&'b'));
+        assert_eq!(m.get(1000), Some(&'c'));
+        assert!(m.contains_key(&2));
+        assert!(!m.contains_key(9));
+        assert_eq!(m.get_key_value(7), Some((&interval!(5, 8, "(]"), &'b')));
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut m = IntervalMap::new([
+            (interval!(1, 10), 'a'),
+            (interval!(20, 30), 'b'),
+        ]);
+        let m2 = m.clone();
+        m.remove(interval!(4, 6));
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![
+                (&interval!(1, 4), &'a'),
+                (&interval!(6, 10), &'a'),
+                (&interval!(20, 30), &'b'),
+            ],
+        );
+        assert_ne!(m, m2);
+
+        m.remove(interval!(8, 25));
+        assert_eq!(
+            m.iter().collect::<Vec<_>>(),
+            vec![
+                (&interval!(1, 4), &'a'),
+                (&interval!(6, 8), &'a'),
+                (&interval!(25, 30), &'b'),
+            ],
+        );
+
+        m.remove(Interval::empty());
+        assert_eq!(m.len(), 3);
+
+        m.remove(interval!(0, 100));
+        assert_eq!(m, IntervalMap::default());
+    }
+
+    #[test]
+    fn test_insert_many() {
+        let mut m = IntervalMap::new(
+            (0..20).map(|i| (interval!(i * 10, i * 10 + 5), i)),
+        );
+        assert_eq!(m.len(), 20);
+
+        // Insert in a gap, without overlapping
+        m.insert(interval!(26, 28), 100);
+        assert_eq!(m.len(), 21);
+        assert_eq!(m.get_key_value(27), Some((&interval!(26, 28), &100)));
+
+        // Override several intervals, keeping parts on both sides
+        m.insert(interval!(42, 73), 200);
+        assert_eq!(
+            m.range(interval!(40, 80)).collect::<Vec<_>>(),
+            vec![
+                (&interval!(40, 42), &4),
+                (&interval!(42, 73), &200),
+                (&interval!(73, 75), &7),
+            ],
+        );
+        assert_eq!(m.len(), 20);
+        assert_eq!(m.get(35), None);
+        assert_eq!(m.get(80), Some(&8));
+    }
+
+    #[test]
+    fn test_range() {
+        let m = IntervalMap::new([
+            (interval!(1, 3), 'a'),
+            (interval!(5, 8), 'b'),
+            (interval!(10, 12), 'c'),
+        ]);
+        assert_eq!(m.range(interval!(3, 5)).count(), 0);
+        assert_eq!(m.range(Interval::empty()).count(), 0);
+        assert_eq!(
+            m.range(interval!(7, 10, "[]")).collect::<Vec<_>>(),
+            vec![(&interval!(5, 8), &'b'), (&interval!(10, 12), &'c')],
+        );
+        assert_eq!(m.range(Interval::doubly_unbounded()).count(), 3);
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod streaming {
+    use crate::*;
+
+    #[test]
+    fn test_coalesce() {
+        let intvs = vec![
+            interval!(1, 3),
+            Interval::empty(),
+            interval!(2, 4),
+            interval!(3, 4, "[]"),
+            interval!(5, 6),
+            interval!(6, 8, "()"),
+            interval!(9, 10),
+        ];
+        assert_eq!(
+            coalesce(intvs).collect::<Vec<_>>(),
+            [interval!(1, 6), interval!(6, 8, "()"), interval!(9, 10)],
+        );
+        assert_eq!(coalesce(Vec::<Interval<u32>>::new()).count(), 0);
+        assert_eq!(coalesce([Interval::<u32>::empty()]).count(), 0);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let m1 = IntervalSet::new_joining([
+            interval!(3, 10, "[]"),
+            interval!(15, 20, "()"),
+            interval!(25, 40, "[)"),
+        ]);
+        let m2 = IntervalSet::new_separating([
+            interval!(1, 4),
+            interval!(8, 16),
+            interval!(16, 27),
+            interval!(38, 50),
+        ]);
+        let left = || m1.iter().cloned();
+        let right = || m2.iter().cloned();
+
+        assert_eq!(
+            union_iter(left(), right()).collect::<Vec<_>>(),
+            m1.union_set(&m2).iter().cloned().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            intersection_iter(left(), right()).collect::<Vec<_>>(),
+            m1.intersection_set(&m2).iter().cloned().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            difference_iter(left(), right()).collect::<Vec<_>>(),
+            m1.difference_set(&m2).iter().cloned().collect::<Vec<_>>(),
+        );
+        assert_eq!(
+            difference_iter(right(), left()).collect::<Vec<_>>(),
+            IntervalSet::new_joining(m2.difference_set(&m1).iter().cloned())
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(difference_iter(left(), left()).count(), 0);
+        assert_eq!(
+            difference_iter(left(), []).collect::<Vec<_>>(),
+            left().collect::<Vec<_>>(),
+        );
+        assert_eq!(intersection_iter(left(), []).count(), 0);
+        assert_eq!(
+            union_iter([], left()).collect::<Vec<_>>(),
+            left().collect::<Vec<_>>(),
+        );
+
+        // Inputs might overlap
+        assert_eq!(
+            difference_iter(
+                [interval!(1, 10), interval!(5, 20)],
+                [interval!(2, 3), interval!(2, 6), interval!(15, 16)],
+            )
+            .collect::<Vec<_>>(),
+            [interval!(1, 2), interval!(6, 15), interval!(16, 20)],
+        );
+        assert_eq!(
+            intersection_iter(
+                [interval!(1, 10), interval!(5, 20)],
+                [interval!(2, 3), interval!(2, 6), interval!(15, 30)],
+            )
+            .collect::<Vec<_>>(),
+            [interval!(2, 6), interval!(15, 20)],
+        );
+    }
+
+    #[test]
+    fn test_merge_all() {
+        let iters = vec![
+            vec![interval!(1, 3), interval!(10, 12)],
+            vec![],
+            vec![interval!(2, 5), Interval::empty()],
+            vec![interval!(6, 7), interval!(12, 30)],
+        ];
+        assert_eq!(
+            merge_all(iters).collect::<Vec<_>>(),
+            [interval!(1, 5), interval!(6, 7), interval!(10, 30)],
+        );
+        assert_eq!(merge_all(Vec::<Vec<Interval<u32>>>::new()).count(), 0);
+
+        // Many iterators, with identical lower bounds across iterators
+        let iters = (0_u32..50)
+            .map(|i| {
+                (0..10)
+                    .map(|j| interval!(j * 100 + i % 7, j * 100 + i % 7 + 2))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let expected =
+            IntervalSet::new_joining(iters.iter().flatten().cloned());
+        assert_eq!(
+            merge_all(iters).collect::<Vec<_>>(),
+            expected.iter().cloned().collect::<Vec<_>>(),
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod btree {
+    use crate::multi::Policy;
+    use crate::*;
+    use ::core::convert::TryFrom;
+
+    /// Apply the same random operations on a Vec-based and a tree-based
+    /// set, and check they always have the same intervals.
+    fn check_same_as_vec<P: Policy<u32> + ::core::fmt::Debug>() {
+        let mut vec = IntervalSet::<u32, P>::empty();
+        let mut tree = BTreeIntervalSet::<u32, P>::empty();
+        let mut seed = 17_u32;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) % 100
+        };
+
+        for _ in 0..2000 {
+            let lo = random();
+            let intv = match random() % 4 {
+                0 => interval!(lo, lo + random() % 10, "[)"),
+                1 => interval!(lo, lo + random() % 10, "[]"),
+                2 => interval!(lo, lo + random() % 10, "()"),
+                _ => interval!(lo, lo + random() % 10, "(]"),
+            };
+            if random() % 3 == 0 {
+                vec.remove_interval(&intv);
+                tree.remove_interval(&intv);
+            } else {
+                vec.add(intv);
+                tree.add(intv);
+            }
+            assert!(vec.iter().eq(tree.iter()), "{:?}\n{:?}", vec, tree);
+
+            let val = random();
+            assert_eq!(vec.find(val), tree.find(val));
+            assert_eq!(vec.contains(val), tree.contains(val));
+            assert!(vec.overlapping(intv).iter().eq(tree.overlapping(&intv)));
+            assert_eq!(
+                vec.contains_interval(intv),
+                tree.contains_interval(intv)
+            );
+            assert_eq!(
+                vec.intersects_interval(intv),
+                tree.intersects_interval(intv)
+            );
+            assert!(vec
+                .intersection_interval(intv)
+                .iter()
+                .eq(tree.intersection_interval(intv).iter()));
+            assert!(vec
+                .difference_interval(intv)
+                .iter()
+                .eq(tree.difference_interval(intv).iter()));
+            assert!(vec
+                .symmetric_difference_interval(intv)
+                .iter()
+                .eq(tree.symmetric_difference_interval(intv).iter()));
+            assert!(vec.complement().iter().eq(tree.complement().iter()));
+            assert!(vec.gaps().eq(tree.gaps()));
+            assert!((&vec & intv).iter().eq((&tree & intv).iter()));
+            assert!((&vec | intv).iter().eq((&tree | intv).iter()));
+            assert_eq!(vec.left_of(val), tree.left_of(val));
+            assert_eq!(vec.right_of(val), tree.right_of(val));
+            assert_eq!(
+                vec.strictly_left_of_interval(intv),
+                tree.strictly_left_of_interval(intv)
+            );
+            assert_eq!(
+                vec.right_of_interval(intv),
+                tree.right_of_interval(intv)
+            );
+
+            if random() % 50 == 0 {
+                check_sets_same_as_vec(&vec, &tree, &mut random);
+            }
+        }
+    }
+
+    /// Combine the sets with another random set, which is also used as an
+    /// IntervalSet to check interoperability.
+    fn check_sets_same_as_vec<P, R>(
+        vec: &IntervalSet<u32, P>,
+        tree: &BTreeIntervalSet<u32, P>,
+        random: &mut R,
+    ) where
+        P: Policy<u32> + ::core::fmt::Debug,
+        R: FnMut() -> u32,
+    {
+        let mut other = IntervalSet::<u32, Joining>::empty();
+        for _ in 0..random() % 8 {
+            let lo = random();
+            other.add(interval!(lo, lo + random() % 20, "[)"));
+        }
+        let other_tree: BTreeIntervalSet<u32> = other.clone().into();
+
+        let same = |v: IntervalSet<u32, P>, t: BTreeIntervalSet<u32, P>| {
+            assert!(v.iter().eq(t.iter()), "{:?}\n{:?}", v, t);
+        };
+        same(vec.union_set(&other), tree.union_set(&other_tree));
+        same(vec.union_set(&other), tree.union_set(&other));
+        same(vec.intersection_set(&other), tree.intersection_set(&other));
+        same(vec.difference_set(&other), tree.difference_set(&other));
+        same(
+            vec.symmetric_difference_set(&other),
+            tree.symmetric_difference_set(&other),
+        );
+        same(vec | &other, tree | &other_tree);
+        same(vec & &other, tree & &other_tree);
+        same(vec - &other, tree - &other_tree);
+        same(vec ^ &other, tree ^ &other_tree);
+        assert_eq!(vec.contains_set(&other), tree.contains_set(&other));
+        assert_eq!(vec.intersects_set(&other), tree.intersects_set(&other));
+        assert_eq!(vec.left_of_set(&other), tree.left_of_set(&other));
+        assert_eq!(vec.right_of_set(&other), tree.right_of_set(&other));
+        assert!(tree.equivalent(vec));
+        assert!(other_tree.equivalent(&other));
+        assert_eq!(vec.total_length(), tree.total_length());
+        assert_eq!(vec.cardinality(), tree.cardinality());
+        assert_eq!(vec.to_string(), tree.to_string());
+        assert_eq!(vec.postgres().to_string(), tree.postgres().to_string());
+        let delta = random() % 5;
+        assert_eq!(vec.shift(&delta), tree.shift(&delta).map(Into::into));
+        assert_eq!(vec.expand(&delta), tree.expand(&delta).map(Into::into));
+        assert_eq!(vec.shrink(&delta), tree.shrink(&delta).map(Into::into));
+        assert_eq!(
+            vec.scale_around(&50, &2),
+            tree.scale_around(&50, &2).map(Into::into),
+        );
+        same(vec.map(|v| v / 2), tree.map(|v| v / 2));
+        same(
+            vec.map_decreasing(|v| 200 - v),
+            tree.map_decreasing(|v| 200 - v),
+        );
+
+        let (mut v, mut t) = (vec.clone(), tree.clone());
+        v ^= &other;
+        t ^= &other_tree;
+        v -= other.clone();
+        t -= other_tree.clone();
+        same(v, t);
+    }
+
+    #[test]
+    fn test_same_as_vec() {
+        check_same_as_vec::<Joining>();
+        check_same_as_vec::<Separating>();
+        check_same_as_vec::<Splitting>();
+        check_same_as_vec::<Canonical<Separating>>();
+    }
+
+    #[test]
+    fn test_btree_api() {
+        let m = BTreeIntervalSet::new_separating([
+            interval!(1, 5),
+            interval!(5, 8),
+        ]);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.gaps().count(), 0);
+        assert!(BTreeIntervalSet::<u32, _>::empty_separating().is_empty());
+        assert!(BTreeIntervalSet::<u32, _>::empty_splitting().is_empty());
+        assert_eq!(
+            BTreeIntervalSet::new_splitting([
+                interval!(1, 10),
+                interval!(5, 8),
+            ])
+            .len(),
+            3
+        );
+        assert_eq!(BTreeIntervalSet::new_single_joining(3).len(), 1);
+        assert_eq!(BTreeIntervalSet::new_single_separating(3).len(), 1);
+        assert_eq!(BTreeIntervalSet::new_single_splitting(3).len(), 1);
+        assert_eq!(
+            BTreeIntervalSet::new_canonical([interval!(1, 5, "[]")]),
+            BTreeIntervalSet::new_single_canonical(1) | interval!(2, 6, "[)"),
+        );
+
+        let m = BTreeIntervalSet::<u32>::from_pair(
+            interval!(1, 20).difference(interval!(5, 10)),
+        );
+        assert_eq!(
+            m,
+            BTreeIntervalSet::new_joining([interval!(1, 5), interval!(10, 20)]),
+        );
+        assert_eq!(
+            m.difference(1),
+            m.difference_interval(interval!(1, 1, "[]"))
+        );
+        assert_eq!(
+            !&m,
+            BTreeIntervalSet::new_joining([
+                interval!("-inf", 1, ")"),
+                interval!(5, 10, "[)"),
+                interval!(20, "[inf"),
+            ]),
+        );
+        assert_eq!(!!&m, m);
+        assert_eq!(
+            !BTreeIntervalSet::<u32>::empty(),
+            BTreeIntervalSet::new_joining([Interval::doubly_unbounded()]),
+        );
+        assert_eq!(
+            m.complement_within(interval!(0, 30)),
+            BTreeIntervalSet::new_joining([
+                interval!(0, 1),
+                interval!(5, 10),
+                interval!(20, 30),
+            ]),
+        );
+        assert_eq!(
+            m.gaps_within(&interval!(0, 15)).collect::<Vec<_>>(),
+            [interval!(0, 1), interval!(5, 10)],
+        );
+        assert!(m.left_of(20));
+        assert!(!m.strictly_left_of(19));
+        assert!(m.left_of_interval(interval!(20, 30)));
+        assert!(m.right_of(1));
+        assert!(!m.strictly_right_of(1));
+        assert!(m.right_of_interval(interval!(0, 1)));
+        assert!(m.intersects_set(BTreeIntervalSet::new_single_joining(3)));
+        assert!(!m.contains_set(IntervalSet::new_single_joining(7)));
+
+        let mut m2 = m.clone();
+        m2 -= interval!(3, 12);
+        m2 |= &interval!(0, 1);
+        m2 &= interval!(0, 15);
+        assert_eq!(
+            m2,
+            BTreeIntervalSet::new_joining([interval!(0, 3), interval!(12, 15)]),
+        );
+        assert_eq!(m2.clone() - m2.clone(), BTreeIntervalSet::empty());
+    }
+
+    #[test]
+    fn test_btree_parse() {
+        let m = BTreeIntervalSet::<u32>::new_joining([
+            interval!(1, 3),
+            interval!(5, "[inf"),
+        ]);
+        assert_eq!(m.to_string(), "{[1, 3), [5,)}");
+        assert_eq!(m.postgres().to_string(), "{[1,3),[5,)}");
+        assert_eq!(BTreeIntervalSet::<u32>::empty().to_string(), "empty");
+        let vec: IntervalSet<u32> = m.clone().into();
+        assert_eq!(m.total_length(), vec.total_length());
+        assert_eq!(m.cardinality(), vec.cardinality());
+        assert_eq!((m.clone() & interval!(0, 10)).total_length(), Some(7),);
+        assert_eq!((m.clone() & interval!(0, 10)).cardinality(), Some(7),);
+
+        assert_eq!(
+            m.to_string().parse::<BTreeIntervalSet<u32>>(),
+            Ok(m.clone())
+        );
+        assert_eq!(
+            BTreeIntervalSet::<u32>::try_from("{[5,), [1,3)}"),
+            Ok(m.clone()),
+        );
+        assert_eq!(
+            BTreeIntervalSet::<u32>::try_from("empty"),
+            Ok(BTreeIntervalSet::empty()),
+        );
+        assert_eq!(
+            BTreeIntervalSet::<u32>::parse_postgres("{[1,3),[5,)}"),
+            Ok(m.clone()),
+        );
+        assert_eq!(
+            BTreeIntervalSet::<u32>::parse_postgres("{[1,3),[5,)}")
+                .map(IntervalSet::from),
+            IntervalSet::<u32>::parse_postgres("{[1,3),[5,)}"),
+        );
+        assert!("{[1,3), x}".parse::<BTreeIntervalSet<u32>>().is_err());
+        assert!(BTreeIntervalSet::<u32>::parse_postgres("{[1,3),").is_err());
+
+        assert_eq!(
+            m.shift(&2),
+            Some(BTreeIntervalSet::new_joining([
+                interval!(3, 5),
+                interval!(7, "[inf"),
+            ])),
+        );
+        assert_eq!(
+            m.expand(&1),
+            Some(BTreeIntervalSet::new_joining([interval!(0, "[inf")])),
+        );
+        assert_eq!(
+            m.shrink(&1),
+            Some(BTreeIntervalSet::new_joining([interval!(6, "[inf")])),
+        );
+        assert_eq!(m.expand(&2), None);
+        assert_eq!(
+            m.map(|v| u64::from(*v) * 10),
+            BTreeIntervalSet::new_joining([
+                interval!(10_u64, 30),
+                interval!(50, "[inf"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_btree() {
+        let mut m = BTreeIntervalSet::empty_joining();
+        assert!(m.is_empty());
+        assert_eq!(m.lower(), None);
+        assert_eq!(m.convex_hull(), Interval::empty());
+        assert_eq!(format!("{}", m), "empty");
+
+        m.extend([interval!(1, 5), interval!(10, 20), interval!(5, 8)]);
+        assert_eq!(m.len(), 2);
+        assert_eq!(m.lower(), Some(&1));
+        assert_eq!(m.upper(), Some(&20));
+        assert!(!m.lower_unbounded());
+        assert!(!m.upper_unbounded());
+        assert_eq!(m.convex_hull(), interval!(1, 20));
+        assert_eq!(format!("{}", m), "{[1, 8), [10, 20)}");
+        assert!(m.contains_interval(interval!(2, 7)));
+        assert!(!m.contains_interval(interval!(2, 12)));
+        assert!(m.contains_interval(Interval::empty()));
+
+        m.remove(3);
+        assert_eq!(m.len(), 3);
+        m.add(Interval::empty());
+        assert_eq!(m.len(), 3);
+
+        let m2 = BTreeIntervalSet::new_joining([
+            interval!(0, 2),
+            interval!(15, "[inf"),
+        ]);
+        assert!(m2.upper_unbounded());
+        assert_eq!(
+            m.union_set(&m2),
+            BTreeIntervalSet::new_joining([
+                interval!(0, 3),
+                interval!(4, 8),
+                interval!(10, "[inf"),
+            ]),
+        );
+        assert_eq!(
+            m.intersection_set(&m2),
+            BTreeIntervalSet::new_joining([interval!(1, 2), interval!(15, 20)]),
+        );
+        assert_eq!(
+            m.difference_set(&m2),
+            BTreeIntervalSet::new_joining([
+                interval!(2, 3),
+                interval!(4, 8),
+                interval!(10, 15),
+            ]),
+        );
+
+        // Conversions
+        let s: IntervalSet<u32> = m.clone().into();
+        assert!(s.iter().eq(m.iter()));
+        let m3: BTreeIntervalSet<u32> = s.into();
+        assert_eq!(m3, m);
+
+        let mut m4 = BTreeIntervalSet::<u32, Separating>::new_single(3);
+        m4.add(interval!(4, 5));
+        assert_eq!(m4.len(), 2);
+        m4.clear();
+        assert!(m4.is_empty());
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod tree {
+    use crate::*;
+
+    #[test]
+    fn test_query() {
+        // All kinds of bounds, including unbounded and overlapping intervals
+        let intvs = [
+            interval!(1, 10, "[)"),
+            interval!(1, 10, "[]"),
+            interval!(5, 20, "()"),
+            interval!(5, 20, "(]"),
+            interval!("-inf", 3, ")"),
+            interval!("-inf", 3, "]"),
+            interval!(15, "[inf"),
+            interval!(15, "(inf"),
+            Interval::doubly_unbounded(),
+            interval!(7, 7, "[]"),
+            interval!(30, 40),
+            Interval::empty(),
+        ];
+        let tree = IntervalTree::new(
+            intvs.iter().cloned().enumerate().map(|(i, e)| (e, i)),
+        );
+        assert_eq!(tree.len(), intvs.len() - 1);
+
+        for point in 0..45 {
+            let mut expected = intvs
+                .iter()
+                .enumerate()
+                .filter(|(_, e)| e.contains(point))
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>();
+            let mut actual =
+                tree.query_point(point).map(|(_, i)| *i).collect::<Vec<_>>();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected, "point={}", point);
+        }
+
+        for lo in 0..42 {
+            for len in 0..10 {
+                for q in [
+                    interval!(lo, lo + len, "[)"),
+                    interval!(lo, lo + len, "(]"),
+                    interval!(lo, lo + len, "[]"),
+                    interval!(lo, lo + len, "()"),
+                ] {
+                    let mut expected = intvs
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, e)| e.intersects(q))
+                        .map(|(i, _)| i)
+                        .collect::<Vec<_>>();
+                    let mut actual = tree
+                        .query_interval(q)
+                        .map(|(_, i)| *i)
+                        .collect::<Vec<_>>();
+                    expected.sort();
+                    actual.sort();
+                    assert_eq!(actual, expected, "query={}", q);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_update() {
+        let mut tree = IntervalTree::empty();
+        assert!(tree.is_empty());
+        assert_eq!(tree.query_point(3).count(), 0);
+
+        tree.insert(interval!(1, 10), 'a');
+        tree.insert(interval!(5, 8), 'b');
+        tree.insert(interval!(1, 10), 'c');
+        tree.insert(Interval::empty(), 'd');
+        assert_eq!(tree.len(), 3);
+        assert_eq!(
+            tree.query_point(6).map(|(_, v)| *v).collect::<Vec<_>>(),
+            ['a', 'c', 'b'],
+        );
+        assert_eq!(tree.query_interval(interval!(8, 9)).count(), 2,);
+
+        assert_eq!(tree.remove(interval!(1, 10)), ['a', 'c']);
+        assert_eq!(tree.remove(interval!(1, 10)), []);
+        assert_eq!(tree.remove(Interval::empty()), []);
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree.query_point(2).count(), 0);
+        assert_eq!(tree.query_point(6).count(), 1);
+
+        tree.extend([(interval!(20, 30), 'e'), (interval!(0, 100), 'f')]);
+        tree.retain(|_, v| *v != 'b');
+        assert_eq!(
+            tree.iter().map(|(_, v)| *v).collect::<Vec<_>>(),
+            ['f', 'e'],
+        );
+        assert_eq!(
+            tree.query_point(6).map(|(_, v)| *v).collect::<Vec<_>>(),
+            ['f'],
+        );
+
+        let tree2 = tree.clone();
+        tree.clear();
+        assert!(tree.is_empty());
+        assert_eq!(tree2.len(), 2);
+
+        // Floats
+        let tree3 = IntervalTree::new([
+            (interval!(1.0, 2.0, "()"), 1),
+            (interval!(2.0, 3.0, "[]"), 2),
+        ]);
+        assert_eq!(
+            tree3.query_point(2.0).map(|(_, v)| *v).collect::<Vec<_>>(),
+            [2]
+        );
+        assert_eq!(tree3.query_point(1.0).count(), 0);
+    }
+
+    /// Apply random insertions and removals, one at a time, and compare
+    /// the results of queries with a naive implementation.
+    #[test]
+    fn test_same_as_vec() {
+        let mut tree = IntervalTree::empty();
+        let mut vec: Vec<(Interval<u32>, u32)> = Vec::new();
+        let mut seed = 5_u32;
+        let mut random = move || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            (seed >> 16) % 200
+        };
+
+        for step in 0..3000 {
+            let lo = random();
+            let intv = interval!(lo, lo + random() % 20, "[]");
+            if random() % 4 == 0 {
+                let mut expected = Vec::new();
+                vec.retain(|(e, v)| {
+                    if *e == intv {
+                        expected.push(*v);
+                    }
+                    *e != intv
+                });
+                assert_eq!(tree.remove(intv), expected);
+            } else {
+                tree.insert(intv, step);
+                vec.push((intv, step));
+            }
+            assert_eq!(tree.len(), vec.len());
+
+            let q = interval!(lo, lo + random() % 10, "[)");
+            let mut expected = vec
+                .iter()
+                .filter(|(e, _)| e.intersects(q))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>();
+            let mut actual =
+                tree.query_interval(q).map(|(_, v)| *v).collect::<Vec<_>>();
+            expected.sort();
+            actual.sort();
+            assert_eq!(actual, expected, "query={}", q);
+        }
+
+        // Iteration is sorted, and equal intervals are kept in insertion
+        // order.
+        vec.sort_by(|(e1, _), (e2, _)| {
+            e1.partial_cmp(e2).unwrap_or(::core::cmp::Ordering::Equal)
+        });
+        assert!(tree.iter().eq(vec.iter().map(|(e, v)| (e, v))));
+
+        // Queries are lazy iterators, which can be partially consumed
+        let mut iter = tree.query_point(100);
+        let first = iter.next();
+        assert!(first.is_some_and(|(e, _)| e.contains(100)));
+        assert!(iter.all(|(e, _)| e.contains(100)));
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg(test)]
+mod rigorous {
+    use crate::rigorous::{next_down, next_up};
+    use crate::*;
+
+    fn r(lo: f64, hi: f64) -> Interval<Rigorous> {
+        Interval::new_closed_closed(Rigorous(lo), Rigorous(hi))
+    }
+
+    fn value(bound: Option<&Rigorous>) -> f64 {
+        bound.map_or(f64::NAN, |v| v.0)
+    }
+
+    #[test]
+    fn test_next_float() {
+        assert_eq!(next_up(1.0), 1.0 + f64::EPSILON);
+        assert_eq!(next_down(1.0 + f64::EPSILON), 1.0);
+        assert_eq!(next_up(-0.0), f64::from_bits(1));
+        assert_eq!(next_down(0.0), -f64::from_bits(1));
+        assert_eq!(next_up(-f64::from_bits(1)), 0.0);
+        assert_eq!(next_up(f64::MAX), f64::INFINITY);
+        assert_eq!(next_up(f64::INFINITY), f64::INFINITY);
+        assert_eq!(next_down(f64::NEG_INFINITY), f64::NEG_INFINITY);
+        assert_eq!(next_up(f64::NEG_INFINITY), f64::MIN);
+        assert!(next_up(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        // Results are rounded outward, even when the float result is exact
+        let sum = (r(1.0, 2.0) + r(0.5, 1.0)).unwrap_or_default();
+        assert_eq!(sum.lower(), Some(&Rigorous(next_down(1.5))));
+        assert_eq!(sum.upper(), Some(&Rigorous(next_up(3.0))));
+
+        let tenth = r(0.1, 0.1);
+        let mut total = r(0.0, 0.0);
+        for _ in 0..10 {
+            total = (&total + &tenth).unwrap_or_default();
+        }
+        assert!(total.contains(Rigorous(1.0)));
+        // Open and unbounded bounds are preserved
+        let product = (Interval::new_open_unbounded(Rigorous(2.0))
+            * r(3.0, 4.0))
+        .unwrap_or_default();
+        assert_eq!(product.lower(), Some(&Rigorous(next_down(6.0))));
+        assert!(!product.lower_inclusive());
+        assert!(product.upper_unbounded());
+
+        // Overflow gives a result that still contains the exact value
+        let big = (r(f64::MAX, f64::MAX) * Rigorous(2.0)).unwrap_or_default();
+        assert_eq!(big.lower(), Some(&Rigorous(f64::MAX)));
+        assert_eq!(big.upper(), Some(&Rigorous(f64::INFINITY)));
+
+        // Intervals that contain no float are not empty
+        let tiny = Interval::new_open_open(
+            Rigorous(1.0),
+            Rigorous(1.0 + f64::EPSILON),
+        );
+        assert!(!tiny.is_empty());
+        assert!(!Interval::new_open_unbounded(Rigorous(f64::MAX)).is_empty());
+    }
+
+    #[test]
+    fn test_functions() {
+        let intvs = [
+            r(-3.0, -1.0),
+            r(-1.0, 2.0),
+            r(0.0, 1.0),
+            r(0.1, 0.7),
+            r(1.0, 5.0),
+            r(2.0, 3.0),
+            r(100.0, 106.0),
+            r(1e6, 1e6 + 1.0),
+            Interval::new_open_closed(Rigorous(0.0), Rigorous(4.0)),
+        ];
+        let functions: [(
+            &str,
+            fn(&Interval<Rigorous>) -> Interval<Rigorous>,
+            fn(f64) -> f64,
+        ); 8] = [
+            ("sqrt", Interval::sqrt, f64::sqrt),
+            ("exp", Interval::exp, f64::exp),
+            ("ln", Interval::ln, f64::ln),
+            ("sin", Interval::sin, f64::sin),
+            ("cos", Interval::cos, f64::cos),
+            ("powi(2)", |i| i.powi(2), |x| x.powi(2)),
+            ("powi(3)", |i| i.powi(3), |x| x.powi(3)),
+            ("powi(0)", |i| i.powi(0), |x| x.powi(0)),
+        ];
+        for intv in &intvs {
+            for (name, enclosing, f) in &functions {
+                let result = enclosing(intv);
+                for step in 0..=1000 {
+                    let (lo, hi) = (value(intv.lower()), value(intv.upper()));
+                    let x = lo + (hi - lo) * f64::from(step) / 1000.0;
+                    let y = f(x);
+                    if intv.contains(Rigorous(x)) && !y.is_nan() {
+                        assert!(
+                            result.contains(Rigorous(y)),
+                            "{}({}) = {} not in {}",
+                            name,
+                            x,
+                            y,
+                            result,
+                        );
+                    }
+                }
+            }
+        }
+
+        // Results are tight
+        assert_eq!(r(-1.0, 4.0).sqrt().lower(), Some(&Rigorous(0.0)));
+        assert!(r(-2.0, -1.0).sqrt().is_empty());
+        assert_eq!(r(0.0, 2.0).sin().upper(), Some(&Rigorous(1.0)));
+        assert_eq!(r(4.0, 5.0).sin().lower(), Some(&Rigorous(-1.0)));
+        assert!(value(r(0.0, 1.0).sin().upper()) < 0.85);
+        assert_eq!(r(0.0, 10.0).cos(), r(-1.0, 1.0));
+        assert_eq!(
+            Interval::<Rigorous>::doubly_unbounded().sin(),
+            r(-1.0, 1.0),
+        );
+        assert_eq!(r(-2.0, 1.0).powi(2).lower(), Some(&Rigorous(0.0)));
+        assert!(value(r(-2.0, 1.0).powi(2).upper()) < 4.0001);
+        assert_eq!(r(-2.0, 1.0).powi(0), r(1.0, 1.0));
+
+        // Unbounded intervals
+        let exp = Interval::new_unbounded_closed(Rigorous(0.0)).exp();
+        assert_eq!(exp.lower(), Some(&Rigorous(0.0)));
+        assert!(!exp.lower_inclusive());
+        assert!(Interval::new_open_closed(Rigorous(0.0), Rigorous(1.0))
+            .ln()
+            .lower_unbounded());
+        assert!(Interval::new_unbounded_closed(Rigorous(-1.0))
+            .powi(3)
+            .lower_unbounded());
+        assert!(Interval::new_unbounded_closed(Rigorous(-1.0))
+            .powi(2)
+            .upper_unbounded());
+        assert!(Interval::<Rigorous>::empty().exp().is_empty());
     }
 }
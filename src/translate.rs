@@ -0,0 +1,228 @@
+//! Moving and resizing intervals.
+//!
+//! Contrary to interval arithmetic (see [`Interval::checked_add()`]), those
+//! operations apply to the bounds themselves, and thus preserve their kind
+//! (open, closed or unbounded).  The type of the delta might be different
+//! from the type of the bounds, for instance a `TimeDelta` for dates.
+
+use crate::arithmetic::Arithmetic;
+use crate::bounds::Bound;
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use ::core::cmp::Ordering;
+
+/// Moves a value by a delta of type D.
+/// Both operations return None when the result cannot be represented,
+/// for instance on overflow.
+/// ```
+/// use rust_intervals::Translate;
+/// assert_eq!(1_u8.checked_add_delta(&2), Some(3));
+/// assert_eq!(1_u8.checked_sub_delta(&2), None);
+/// ```
+pub trait Translate<D = Self>: Sized {
+    /// Returns `self + delta`
+    fn checked_add_delta(&self, delta: &D) -> Option<Self>;
+
+    /// Returns `self - delta`
+    fn checked_sub_delta(&self, delta: &D) -> Option<Self>;
+}
+
+macro_rules! translate_for_int {
+    ($t:tt) => {
+        impl Translate for $t {
+            fn checked_add_delta(&self, delta: &Self) -> Option<Self> {
+                $t::checked_add(*self, *delta)
+            }
+            fn checked_sub_delta(&self, delta: &Self) -> Option<Self> {
+                $t::checked_sub(*self, *delta)
+            }
+        }
+    };
+}
+
+/// For floats, operations fail when they return NaN, or when they overflow,
+/// i.e. return an infinite value from finite operands.
+macro_rules! translate_for_float {
+    ($t:tt) => {
+        impl Translate for $t {
+            fn checked_add_delta(&self, delta: &Self) -> Option<Self> {
+                checked_float!(self, delta, self + delta)
+            }
+            fn checked_sub_delta(&self, delta: &Self) -> Option<Self> {
+                checked_float!(self, delta, self - delta)
+            }
+        }
+    };
+}
+
+macro_rules! checked_float {
+    ($value:expr, $delta:expr, $result:expr) => {{
+        let result = $result;
+        if result.is_nan()
+            || (!result.is_finite() && $value.is_finite() && $delta.is_finite())
+        {
+            None
+        } else {
+            Some(result)
+        }
+    }};
+}
+
+translate_for_int!(u8);
+translate_for_int!(u16);
+translate_for_int!(u32);
+translate_for_int!(u64);
+translate_for_int!(u128);
+translate_for_int!(i8);
+translate_for_int!(i16);
+translate_for_int!(i32);
+translate_for_int!(i64);
+translate_for_int!(i128);
+translate_for_int!(usize);
+translate_for_int!(isize);
+translate_for_float!(f32);
+translate_for_float!(f64);
+
+impl<T> Interval<T> {
+    /// Creates an interval from bounds computed by a checked operation.
+    fn from_checked_bounds(
+        lower: Option<Bound<T>>,
+        upper: Option<Bound<T>>,
+    ) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween,
+    {
+        let (lower, upper) = (lower?, upper?);
+        if lower >= upper {
+            Some(Interval::empty())
+        } else {
+            Some(Interval { lower, upper })
+        }
+    }
+
+    /// Moves both bounds by delta.
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(1, 5, "(]");
+    ///    assert_eq!(intv1.shift(&10), Some(interval!(11, 15, "(]")));
+    ///    assert_eq!(intv1.shift(&-1), Some(interval!(0, 4, "(]")));
+    ///    assert_eq!(interval!(1_u8, 5).shift(&255), None);
+    /// ```
+    pub fn shift<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Translate<D>,
+    {
+        if self.is_empty() {
+            return Some(Interval::empty());
+        }
+        let mut f = |v: &T| v.checked_add_delta(delta);
+        Interval::from_checked_bounds(
+            self.lower.checked_map(&mut f),
+            self.upper.checked_map(&mut f),
+        )
+    }
+
+    /// Widens the interval by delta on both sides.
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(5, 10, "[)");
+    ///    assert_eq!(intv1.expand(&2), Some(interval!(3, 12, "[)")));
+    ///    assert_eq!(interval!(5, "inf").expand(&2), Some(interval!(3, "inf")));
+    ///    assert_eq!(interval!(1_u8, 5).expand(&2), None);
+    /// ```
+    pub fn expand<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Translate<D>,
+    {
+        if self.is_empty() {
+            return Some(Interval::empty());
+        }
+        Interval::from_checked_bounds(
+            self.lower.checked_map(&mut |v| v.checked_sub_delta(delta)),
+            self.upper.checked_map(&mut |v| v.checked_add_delta(delta)),
+        )
+    }
+
+    /// Narrows the interval by delta on both sides.  The result is empty if
+    /// the interval is not wide enough, even if one of the bounds cannot be
+    /// moved without overflowing.
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(5, 10, "[)");
+    ///    assert_eq!(intv1.shrink(&2), Some(interval!(7, 8, "[)")));
+    ///    assert_eq!(intv1.shrink(&3), Some(Interval::empty()));
+    ///    assert_eq!(
+    ///        interval!(250_u8, 255, "[]").shrink(&10),
+    ///        Some(Interval::empty()),
+    ///    );
+    /// ```
+    pub fn shrink<D>(&self, delta: &D) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Translate<D>,
+    {
+        if self.is_empty() {
+            return Some(Interval::empty());
+        }
+        let lower = self.lower.checked_map(&mut |v| v.checked_add_delta(delta));
+        let upper = self.upper.checked_map(&mut |v| v.checked_sub_delta(delta));
+        match (lower, upper) {
+            // If one bound moved past the other's original position, the
+            // interval is narrower than twice delta, whatever the other one.
+            (None, Some(upper)) if upper <= self.lower => {
+                Some(Interval::empty())
+            }
+            (Some(lower), None) if lower >= self.upper => {
+                Some(Interval::empty())
+            }
+            (lower, upper) => Interval::from_checked_bounds(lower, upper),
+        }
+    }
+
+    /// Scales the interval by factor, keeping pivot at the same place, i.e.
+    /// each bound b becomes `pivot + (b - pivot) * factor`.
+    /// With a negative factor, the bounds are swapped, as well as their
+    /// inclusiveness.  With a zero factor, the result only contains pivot.
+    /// Returns None on overflow.
+    /// ```
+    /// #  use rust_intervals::{interval, Interval};
+    ///    let intv1 = interval!(2, 4, "[)");
+    ///    assert_eq!(intv1.scale_around(&0, &3), Some(interval!(6, 12, "[)")));
+    ///    assert_eq!(intv1.scale_around(&2, &3), Some(interval!(2, 8, "[)")));
+    ///    assert_eq!(intv1.scale_around(&0, &-1), Some(interval!(-4, -2, "(]")));
+    ///    assert_eq!(intv1.scale_around(&3, &0), Some(interval!(3, 3, "[]")));
+    /// ```
+    pub fn scale_around(&self, pivot: &T, factor: &T) -> Option<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone + Arithmetic,
+    {
+        if self.is_empty() {
+            return Some(Interval::empty());
+        }
+        // Avoid negative differences, which unsigned types cannot represent
+        let f = |v: &T| {
+            if *v >= *pivot {
+                pivot.checked_add(&v.checked_sub(pivot)?.checked_mul(factor)?)
+            } else {
+                pivot.checked_sub(&pivot.checked_sub(v)?.checked_mul(factor)?)
+            }
+        };
+        match factor.partial_cmp(&T::zero())? {
+            Ordering::Equal => Some(Interval::new_single(pivot.clone())),
+            Ordering::Greater => Interval::from_checked_bounds(
+                self.lower.checked_map(&mut |v| Some(f(v)?.round_down())),
+                self.upper.checked_map(&mut |v| Some(f(v)?.round_up())),
+            ),
+            Ordering::Less => Interval::from_checked_bounds(
+                self.upper
+                    .checked_map(&mut |v| Some(f(v)?.round_down()))
+                    .map(Bound::mirror),
+                self.lower
+                    .checked_map(&mut |v| Some(f(v)?.round_up()))
+                    .map(Bound::mirror),
+            ),
+        }
+    }
+}
@@ -0,0 +1,566 @@
+use crate::bounds::Bound;
+use crate::intervals::Interval;
+use crate::nothing_between::NothingBetween;
+use ::core::cmp::Ordering;
+
+/// A collection of possibly overlapping intervals, each associated with a
+/// value.
+///
+/// Contrary to [`crate::IntervalSet`] and [`crate::IntervalMap`], intervals
+/// are never merged or split, so this can be used to find all the intervals
+/// that contain a given point (stabbing queries), or that overlap a given
+/// interval.
+///
+/// The intervals are stored in a balanced (AVL) binary search tree sorted by
+/// lower bound, in which each node also stores the maximal upper bound of its
+/// subtree.  Inserting or removing an interval runs in `O(log n)`.  Queries
+/// are lazy, and return the first result in `O(log n)`, then each of the k
+/// results in `O(log n)` at worst.  Building the tree in a single call to
+/// [`IntervalTree::new()`] or [`IntervalTree::extend()`] runs in
+/// `O(n log n)`.
+/// ```
+/// #  use rust_intervals::{interval, IntervalTree};
+///    let tree = IntervalTree::new([
+///        (interval!(1, 10), 'a'),
+///        (interval!(5, 20), 'b'),
+///        (interval!(15, 30), 'c'),
+///    ]);
+///    assert_eq!(
+///        tree.query_point(7).collect::<Vec<_>>(),
+///        [(&interval!(1, 10), &'a'), (&interval!(5, 20), &'b')],
+///    );
+/// ```
+#[derive(Debug)]
+pub struct IntervalTree<T, V> {
+    root: Link<T, V>,
+    len: usize,
+}
+
+type Link<T, V> = Option<Box<Node<T, V>>>;
+
+#[derive(Clone, Debug)]
+struct Node<T, V> {
+    intv: Interval<T>,
+    value: V,
+
+    // The maximal upper bound of the subtree rooted at this node
+    max_upper: Bound<T>,
+
+    // The height of the subtree rooted at this node (1 for a leaf)
+    height: u32,
+
+    // Intervals on the left are less than intv, intervals on the right are
+    // greater or equal (so equal intervals are kept in insertion order).
+    left: Link<T, V>,
+    right: Link<T, V>,
+}
+
+impl<T, V> IntervalTree<T, V> {
+    /// Returns an empty tree
+    #[must_use]
+    pub fn empty() -> Self {
+        IntervalTree { root: None, len: 0 }
+    }
+
+    /// Create a tree from a collection of intervals and their values.
+    /// Those intervals do not have to be sorted.  Empty intervals are
+    /// ignored, since they contain no value and overlap no interval, as well
+    /// as intervals whose bounds cannot be compared.
+    pub fn new<I>(iter: I) -> Self
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        I: IntoIterator<Item = (Interval<T>, V)>,
+    {
+        let mut tree = IntervalTree::empty();
+        tree.extend(iter);
+        tree
+    }
+
+    /// Return the number of intervals in self.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if there are no intervals in self
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Removes all intervals from the tree
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.len = 0;
+    }
+
+    /// Add an interval and its value to the tree.  Existing intervals are
+    /// left unchanged, even if they are equal to intv.
+    /// Empty intervals are ignored.
+    pub fn insert(&mut self, intv: Interval<T>, value: V)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        if !Self::is_valid(&intv) {
+            return;
+        }
+        self.root = Some(Node::insert(self.root.take(), intv, value));
+        self.len += 1;
+    }
+
+    /// Add multiple intervals and their values to the tree.
+    pub fn extend<I>(&mut self, iter: I)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        I: IntoIterator<Item = (Interval<T>, V)>,
+    {
+        let mut entries = Vec::with_capacity(self.len);
+        Node::into_sorted(self.root.take(), &mut entries);
+        let count = entries.len();
+        entries
+            .extend(iter.into_iter().filter(|(intv, _)| Self::is_valid(intv)));
+        if entries.len() != count {
+            // The sort is stable, so equal intervals are kept in their
+            // insertion order.  All intervals can be compared, so this is
+            // a total order.
+            entries.sort_by(|(e1, _), (e2, _)| {
+                e1.partial_cmp(e2).unwrap_or(Ordering::Equal)
+            });
+        }
+        self.build(entries);
+    }
+
+    /// Whether intv can be stored in the tree: it must not be empty, and
+    /// its bounds must be comparable (so not NaN), so that sorting entries
+    /// does not panic.
+    fn is_valid(intv: &Interval<T>) -> bool
+    where
+        T: PartialOrd + NothingBetween,
+    {
+        !intv.is_empty() && intv.partial_cmp(intv).is_some()
+    }
+
+    /// Remove all entries for which the interval is equal to intv, and
+    /// return their values.
+    pub fn remove<U>(&mut self, intv: U) -> Vec<V>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let u = intv.borrow();
+        let mut removed = Vec::new();
+        if u.is_empty() {
+            return removed;
+        }
+        loop {
+            let (root, value) = Node::remove_first(self.root.take(), u);
+            self.root = root;
+            match value {
+                None => return removed,
+                Some(v) => removed.push(v),
+            }
+            self.len -= 1;
+        }
+    }
+
+    /// Only keep the entries for which f returns true.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        F: FnMut(&Interval<T>, &V) -> bool,
+    {
+        let mut entries = Vec::with_capacity(self.len);
+        Node::into_sorted(self.root.take(), &mut entries);
+        entries.retain(|(e, v)| f(e, v));
+        self.build(entries);
+    }
+
+    /// Replace the contents of self with the sorted entries.
+    fn build(&mut self, entries: Vec<(Interval<T>, V)>)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        self.len = entries.len();
+        self.root = Node::from_sorted(&mut entries.into_iter(), self.len);
+    }
+
+    /// Iterate over all intervals and their values, sorted by lower bound.
+    pub fn iter(&self) -> impl Iterator<Item = (&Interval<T>, &V)> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left(self.root.as_deref());
+        iter
+    }
+
+    /// Iterate over all intervals that contain value, sorted by lower bound.
+    pub fn query_point<K>(
+        &self,
+        value: K,
+    ) -> impl Iterator<Item = (&Interval<T>, &V)>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        K: ::core::borrow::Borrow<T>,
+    {
+        self.query_interval(Interval::new_single(value.borrow().clone()))
+    }
+
+    /// Iterate over all intervals that have at least one value in common
+    /// with intv, sorted by lower bound.
+    /// ```
+    /// #  use rust_intervals::{interval, IntervalTree};
+    ///    let tree = IntervalTree::new([
+    ///        (interval!(1, 10), 'a'),
+    ///        (interval!(5, 20), 'b'),
+    ///        (interval!(15, 30), 'c'),
+    ///    ]);
+    ///    assert_eq!(
+    ///        tree.query_interval(interval!(10, 15)).collect::<Vec<_>>(),
+    ///        [(&interval!(5, 20), &'b')],
+    ///    );
+    /// ```
+    pub fn query_interval<U>(
+        &self,
+        intv: U,
+    ) -> impl Iterator<Item = (&Interval<T>, &V)>
+    where
+        T: PartialOrd + NothingBetween,
+        U: ::core::borrow::Borrow<Interval<T>>,
+    {
+        let mut iter = QueryIter {
+            stack: Vec::new(),
+            intv,
+        };
+        if !iter.intv.borrow().is_empty() {
+            iter.push_left(self.root.as_deref());
+        }
+        iter
+    }
+}
+
+impl<T, V> Node<T, V> {
+    fn height(link: &Link<T, V>) -> u32 {
+        link.as_ref().map_or(0, |n| n.height)
+    }
+
+    /// Recompute the height and maximal upper bound after the children
+    /// have changed.
+    fn update(&mut self)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        self.height =
+            1 + Self::height(&self.left).max(Self::height(&self.right));
+        self.max_upper = self.intv.upper.clone();
+        for child in self.left.iter().chain(self.right.iter()) {
+            if child.max_upper > self.max_upper {
+                self.max_upper = child.max_upper.clone();
+            }
+        }
+    }
+
+    fn rotate_left(mut node: Box<Self>) -> Box<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match node.right.take() {
+            None => node,
+            Some(mut right) => {
+                node.right = right.left.take();
+                node.update();
+                right.left = Some(node);
+                right.update();
+                right
+            }
+        }
+    }
+
+    fn rotate_right(mut node: Box<Self>) -> Box<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match node.left.take() {
+            None => node,
+            Some(mut left) => {
+                node.left = left.right.take();
+                node.update();
+                left.right = Some(node);
+                left.update();
+                left
+            }
+        }
+    }
+
+    /// Restore the AVL invariant after one of the children of node has
+    /// changed height by at most one.
+    fn balance(mut node: Box<Self>) -> Box<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        node.update();
+        let left = Self::height(&node.left);
+        let right = Self::height(&node.right);
+        if left > right + 1 {
+            if let Some(l) = node.left.take() {
+                node.left =
+                    Some(if Self::height(&l.left) < Self::height(&l.right) {
+                        Self::rotate_left(l)
+                    } else {
+                        l
+                    });
+            }
+            Self::rotate_right(node)
+        } else if right > left + 1 {
+            if let Some(r) = node.right.take() {
+                node.right =
+                    Some(if Self::height(&r.right) < Self::height(&r.left) {
+                        Self::rotate_right(r)
+                    } else {
+                        r
+                    });
+            }
+            Self::rotate_left(node)
+        } else {
+            node
+        }
+    }
+
+    /// Insert intv in the subtree, after all intervals equal to it.
+    fn insert(link: Link<T, V>, intv: Interval<T>, value: V) -> Box<Self>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match link {
+            None => Box::new(Node {
+                max_upper: intv.upper.clone(),
+                intv,
+                value,
+                height: 1,
+                left: None,
+                right: None,
+            }),
+            Some(mut node) => {
+                if node.intv <= intv {
+                    node.right =
+                        Some(Self::insert(node.right.take(), intv, value));
+                } else {
+                    node.left =
+                        Some(Self::insert(node.left.take(), intv, value));
+                }
+                Self::balance(node)
+            }
+        }
+    }
+
+    /// Remove the first entry (in insertion order) whose interval is equal
+    /// to intv from the subtree, and return its value.
+    fn remove_first(
+        link: Link<T, V>,
+        intv: &Interval<T>,
+    ) -> (Link<T, V>, Option<V>)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        let mut node = match link {
+            None => return (None, None),
+            Some(node) => node,
+        };
+        let value = match node.intv.partial_cmp(intv) {
+            None => return (Some(node), None),
+            Some(Ordering::Less) => {
+                let (right, value) =
+                    Self::remove_first(node.right.take(), intv);
+                node.right = right;
+                value
+            }
+            Some(Ordering::Greater) => {
+                let (left, value) = Self::remove_first(node.left.take(), intv);
+                node.left = left;
+                value
+            }
+            Some(Ordering::Equal) => {
+                // Equal intervals inserted earlier are on the left
+                let (left, value) = Self::remove_first(node.left.take(), intv);
+                node.left = left;
+                if value.is_none() {
+                    let Node {
+                        value, left, right, ..
+                    } = *node;
+                    let root = match right {
+                        None => left,
+                        Some(right) => {
+                            let (right, mut min) = Self::remove_min(right);
+                            min.left = left;
+                            min.right = right;
+                            Some(Self::balance(min))
+                        }
+                    };
+                    return (root, Some(value));
+                }
+                value
+            }
+        };
+        (Some(Self::balance(node)), value)
+    }
+
+    /// Detach the leftmost node of the subtree.  Its children are left
+    /// empty.
+    fn remove_min(mut node: Box<Self>) -> (Link<T, V>, Box<Self>)
+    where
+        T: PartialOrd + NothingBetween + Clone,
+    {
+        match node.left.take() {
+            None => (node.right.take(), node),
+            Some(left) => {
+                let (left, min) = Self::remove_min(left);
+                node.left = left;
+                (Some(Self::balance(node)), min)
+            }
+        }
+    }
+
+    /// Move all entries of the subtree, in order, to the end of entries.
+    fn into_sorted(link: Link<T, V>, entries: &mut Vec<(Interval<T>, V)>) {
+        if let Some(node) = link {
+            let Node {
+                intv,
+                value,
+                left,
+                right,
+                ..
+            } = *node;
+            Self::into_sorted(left, entries);
+            entries.push((intv, value));
+            Self::into_sorted(right, entries);
+        }
+    }
+
+    /// Build a balanced subtree from the next count sorted entries.
+    fn from_sorted<I>(iter: &mut I, count: usize) -> Link<T, V>
+    where
+        T: PartialOrd + NothingBetween + Clone,
+        I: Iterator<Item = (Interval<T>, V)>,
+    {
+        if count == 0 {
+            return None;
+        }
+        let left = Self::from_sorted(iter, count / 2);
+        let (intv, value) = iter.next()?;
+        let right = Self::from_sorted(iter, count - count / 2 - 1);
+        let mut node = Box::new(Node {
+            max_upper: intv.upper.clone(),
+            intv,
+            value,
+            height: 1,
+            left,
+            right,
+        });
+        node.update();
+        Some(node)
+    }
+}
+
+/// Iterates over all entries of a tree, in order.
+struct Iter<'a, T, V> {
+    // The nodes whose left subtree has already been visited
+    stack: Vec<&'a Node<T, V>>,
+}
+
+impl<'a, T, V> Iter<'a, T, V> {
+    fn push_left(&mut self, mut node: Option<&'a Node<T, V>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T, V> Iterator for Iter<'a, T, V> {
+    type Item = (&'a Interval<T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left(node.right.as_deref());
+        Some((&node.intv, &node.value))
+    }
+}
+
+/// Iterates over the entries of a tree that overlap an interval, in order.
+/// Subtrees in which no interval ends after the start of intv are skipped.
+struct QueryIter<'a, T, V, U> {
+    // The nodes whose left subtree has already been visited
+    stack: Vec<&'a Node<T, V>>,
+    intv: U,
+}
+
+impl<'a, T, V, U> QueryIter<'a, T, V, U>
+where
+    T: PartialOrd + NothingBetween,
+    U: ::core::borrow::Borrow<Interval<T>>,
+{
+    fn push_left(&mut self, mut node: Option<&'a Node<T, V>>) {
+        while let Some(n) = node {
+            // No interval in the subtree ends after intv starts
+            if n.max_upper <= self.intv.borrow().lower {
+                return;
+            }
+            self.stack.push(n);
+            node = n.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T, V, U> Iterator for QueryIter<'a, T, V, U>
+where
+    T: PartialOrd + NothingBetween,
+    U: ::core::borrow::Borrow<Interval<T>>,
+{
+    type Item = (&'a Interval<T>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+            let intv = self.intv.borrow();
+
+            // This interval and all the ones after it start after intv
+            if node.intv.lower >= intv.upper {
+                self.stack.clear();
+                return None;
+            }
+            let found = node.intv.intersects(intv);
+            self.push_left(node.right.as_deref());
+            if found {
+                return Some((&node.intv, &node.value));
+            }
+        }
+    }
+}
+
+impl<T, V> Default for IntervalTree<T, V> {
+    fn default() -> Self {
+        IntervalTree::empty()
+    }
+}
+
+impl<T, V> ::core::clone::Clone for IntervalTree<T, V>
+where
+    T: Clone,
+    V: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T, V> Extend<(Interval<T>, V)> for IntervalTree<T, V>
+where
+    T: PartialOrd + NothingBetween + Clone,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Interval<T>, V)>,
+    {
+        IntervalTree::extend(self, iter);
+    }
+}